@@ -0,0 +1,142 @@
+use crate::game_data::GameTable;
+use crate::tetris_shape::GAMEPLAY_PIECE_COUNT;
+
+/// Number of hidden rows above the visible playfield where pieces spawn
+pub const SPAWN_ROWS: usize = 4;
+
+/// Shape index stored in garbage cells (past the real pieces, so it draws gray)
+pub const GARBAGE_SHAPE_INDEX: i32 = GAMEPLAY_PIECE_COUNT;
+
+/// The locked cells of the playfield, in grid cells only (no pixels)
+/// Each occupied cell remembers the shape index of the piece that left it there
+pub struct Board {
+    width: usize,
+    height: usize, // Total height including spawn area
+    cells: GameTable<i32>,
+}
+
+impl Board {
+    /// Create an empty board with SPAWN_ROWS hidden rows above the visible playfield
+    pub fn new(width: usize, visible_height: usize) -> Self {
+        let height = visible_height + SPAWN_ROWS;
+        Self {
+            width,
+            height,
+            cells: GameTable::new(width, height),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_cell_occupied(&self, cell_x: i32, cell_y: i32) -> bool {
+        // Check bounds
+        if cell_x < 0 || cell_x >= self.width as i32 || cell_y < 0 || cell_y >= self.height as i32 {
+            return true; // Out of bounds counts as occupied
+        }
+        self.cells.has(cell_x, cell_y)
+    }
+
+    /// Check if a piece could sit on these cells (in bounds and not colliding)
+    pub fn fits(&self, cells: &[(i32, i32)]) -> bool {
+        cells
+            .iter()
+            .all(|&(cell_x, cell_y)| !self.is_cell_occupied(cell_x, cell_y))
+    }
+
+    pub fn can_move_down(&self, shape_cells: &[(i32, i32)]) -> bool {
+        let has_cells_above_grid = shape_cells.iter().any(|&(_, y)| y < 0);
+        if has_cells_above_grid {
+            return true; // Always allow movement if any cells are above grid
+        }
+
+        // Check if any cell would move into an occupied cell or out of bounds
+        shape_cells
+            .iter()
+            .all(|&(cell_x, cell_y)| !self.is_cell_occupied(cell_x, cell_y + 1))
+    }
+
+    /// Lock a piece's cells into the board
+    pub fn lock_cells(&mut self, cells: &[(i32, i32)], shape_index: i32) {
+        for &(x, y) in cells {
+            if self.cells.is_valid_position(x, y) {
+                self.cells.set(x, y, shape_index);
+            } else {
+                println!(
+                    "WARNING: Filtered out out-of-bounds cell: ({}, {}) - bounds: width={}, height={}",
+                    x, y, self.width, self.height
+                );
+            }
+        }
+    }
+
+    /// Clear completed lines and shift cells above down
+    /// Returns the number of lines cleared
+    pub fn clear_completed_lines(&mut self) -> usize {
+        let mut cleared_count = 0;
+        let mut row_y = (self.height - 1) as i32;
+
+        // Iterate from bottom to top, checking each row
+        // After removing a row, check the same index again (it now contains what was above)
+        while row_y >= SPAWN_ROWS as i32 {
+            if self.cells.is_row_full(row_y) && self.cells.remove_row_and_shift_down(row_y) {
+                cleared_count += 1;
+                // Don't decrement row_y - the row above has shifted down into this position
+                continue;
+            }
+            // Move to the row above
+            row_y -= 1;
+        }
+
+        cleared_count
+    }
+
+    /// Count the full rows that are garbage (call before `clear_completed_lines`)
+    /// Rows only ever move as a whole, so a row holding any garbage cell is a garbage row
+    pub fn count_full_garbage_rows(&self) -> usize {
+        (0..self.height as i32)
+            .filter(|&row| {
+                self.cells.is_row_full(row)
+                    && self
+                        .cells
+                        .iter_row(row)
+                        .any(|(_, &shape_index)| shape_index == GARBAGE_SHAPE_INDEX)
+            })
+            .count()
+    }
+
+    /// Push the whole stack up one row and fill the bottom row with garbage, except for one hole
+    pub fn push_garbage_row(&mut self, hole_x: i32) {
+        self.cells.shift_up();
+        let bottom_row = self.height as i32 - 1;
+        for cell_x in (0..self.width as i32).filter(|&cell_x| cell_x != hole_x) {
+            self.cells.set(cell_x, bottom_row, GARBAGE_SHAPE_INDEX);
+        }
+    }
+
+    /// Check if the board is completely empty (a perfect clear after `clear_completed_lines`)
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().next().is_none()
+    }
+
+    /// Check if any cells are occupied in the spawn area (above the visible playfield)
+    /// Returns true if game over condition is met (blocks in spawn area)
+    pub fn has_blocks_in_spawn_area(&self) -> bool {
+        (0..SPAWN_ROWS as i32).any(|row| (0..self.width as i32).any(|col| self.cells.has(col, row)))
+    }
+
+    /// All occupied cells as (x, y, shape index)
+    pub fn cells(&self) -> impl Iterator<Item = (i32, i32, i32)> + '_ {
+        self.cells
+            .iter()
+            .map(|(x, y, &shape_index)| (x, y, shape_index))
+    }
+
+    /// Empty the board, returning the cells that were on it
+    pub fn wipe(&mut self) -> Vec<(i32, i32, i32)> {
+        let wiped = self.cells().collect();
+        self.cells.clear();
+        wiped
+    }
+}
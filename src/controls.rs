@@ -0,0 +1,470 @@
+use crate::tetris_mobile_controller::{MobileButton, TetrisMobileController};
+use egor::input::{Input, KeyCode, MouseButton};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Something the player can do, whatever key, mouse button or touch control does it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    SoftDrop,
+    HardDrop,
+    RotateClockwise,
+    RotateCounterClockwise,
+    Rotate180,
+    Hold,
+    Pause,
+    Restart,
+    Quit,
+    /// Start a game from the title screen
+    Start,
+    /// Answer yes in a menu (e.g. confirm restarting or quitting)
+    Confirm,
+    /// Answer no in a menu
+    Cancel,
+}
+
+impl Action {
+    /// Every action, in the order the controls screen lists them
+    pub const ALL: [Action; 14] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::SoftDrop,
+        Action::HardDrop,
+        Action::RotateClockwise,
+        Action::RotateCounterClockwise,
+        Action::Rotate180,
+        Action::Hold,
+        Action::Pause,
+        Action::Restart,
+        Action::Quit,
+        Action::Start,
+        Action::Confirm,
+        Action::Cancel,
+    ];
+
+    /// Name shown on the controls screen
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::SoftDrop => "Soft Drop",
+            Action::HardDrop => "Hard Drop",
+            Action::RotateClockwise => "Rotate CW",
+            Action::RotateCounterClockwise => "Rotate CCW",
+            Action::Rotate180 => "Rotate 180",
+            Action::Hold => "Hold",
+            Action::Pause => "Pause",
+            Action::Restart => "Restart",
+            Action::Quit => "Quit",
+            Action::Start => "Start Game",
+            Action::Confirm => "Menu Yes",
+            Action::Cancel => "Menu No",
+        }
+    }
+
+    /// Where the action is read; a control only has to be unique among actions read together
+    fn context(self) -> ActionContext {
+        match self {
+            Action::Start => ActionContext::Title,
+            Action::Confirm | Action::Cancel => ActionContext::Answer,
+            _ => ActionContext::Gameplay,
+        }
+    }
+
+    /// Bindings a fresh install starts with
+    fn default_bindings(self) -> Vec<Binding> {
+        use Binding::{Key, Mobile, Mouse};
+        match self {
+            Action::MoveLeft => vec![Key(KeyCode::ArrowLeft), Mobile(MobileButton::Left)],
+            Action::MoveRight => vec![Key(KeyCode::ArrowRight), Mobile(MobileButton::Right)],
+            Action::SoftDrop => vec![Key(KeyCode::ArrowDown), Mobile(MobileButton::SoftDrop)],
+            Action::HardDrop => vec![Key(KeyCode::Space), Mobile(MobileButton::HardDrop)],
+            Action::RotateClockwise => vec![
+                Key(KeyCode::ArrowUp),
+                Key(KeyCode::KeyX),
+                Mobile(MobileButton::Rotate),
+            ],
+            Action::RotateCounterClockwise => vec![
+                Key(KeyCode::KeyZ),
+                Key(KeyCode::ControlLeft),
+                Key(KeyCode::ControlRight),
+            ],
+            Action::Rotate180 => vec![Key(KeyCode::KeyA)],
+            Action::Hold => vec![
+                Key(KeyCode::KeyC),
+                Key(KeyCode::ShiftLeft),
+                Key(KeyCode::ShiftRight),
+                Mobile(MobileButton::Hold),
+            ],
+            Action::Pause => vec![
+                Key(KeyCode::KeyP),
+                Key(KeyCode::Escape),
+                Mobile(MobileButton::Pause),
+            ],
+            Action::Restart => vec![Key(KeyCode::KeyR)],
+            Action::Quit => vec![Key(KeyCode::KeyQ)],
+            Action::Start => vec![Key(KeyCode::Enter), Mouse(MouseButton::Left)],
+            Action::Confirm => vec![Key(KeyCode::Enter), Key(KeyCode::KeyY)],
+            Action::Cancel => vec![Key(KeyCode::Escape), Key(KeyCode::KeyN)],
+        }
+    }
+}
+
+/// Group of actions read on the same screen, so their controls must not overlap
+/// Controls may be shared across groups, like Enter for both Start and Confirm
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ActionContext {
+    /// Playing, including pausing, restarting and quitting
+    Gameplay,
+    /// Starting a game from the title screen
+    Title,
+    /// Answering yes or no in the pause menu
+    Answer,
+}
+
+/// A physical control that can trigger an action
+/// Stored by name (e.g. "Space", "Mouse Left", "Touch Hold") so saves don't depend on egor's types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Mobile(MobileButton),
+}
+
+/// Keys that can be bound, with the names used for display and storage
+const KEY_NAMES: [(KeyCode, &str); 49] = [
+    (KeyCode::ArrowLeft, "Left"),
+    (KeyCode::ArrowRight, "Right"),
+    (KeyCode::ArrowUp, "Up"),
+    (KeyCode::ArrowDown, "Down"),
+    (KeyCode::Space, "Space"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Escape, "Esc"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::ShiftLeft, "Left Shift"),
+    (KeyCode::ShiftRight, "Right Shift"),
+    (KeyCode::ControlLeft, "Left Ctrl"),
+    (KeyCode::ControlRight, "Right Ctrl"),
+    (KeyCode::KeyA, "A"),
+    (KeyCode::KeyB, "B"),
+    (KeyCode::KeyC, "C"),
+    (KeyCode::KeyD, "D"),
+    (KeyCode::KeyE, "E"),
+    (KeyCode::KeyF, "F"),
+    (KeyCode::KeyG, "G"),
+    (KeyCode::KeyH, "H"),
+    (KeyCode::KeyI, "I"),
+    (KeyCode::KeyJ, "J"),
+    (KeyCode::KeyK, "K"),
+    (KeyCode::KeyL, "L"),
+    (KeyCode::KeyM, "M"),
+    (KeyCode::KeyN, "N"),
+    (KeyCode::KeyO, "O"),
+    (KeyCode::KeyP, "P"),
+    (KeyCode::KeyQ, "Q"),
+    (KeyCode::KeyR, "R"),
+    (KeyCode::KeyS, "S"),
+    (KeyCode::KeyT, "T"),
+    (KeyCode::KeyU, "U"),
+    (KeyCode::KeyV, "V"),
+    (KeyCode::KeyW, "W"),
+    (KeyCode::KeyX, "X"),
+    (KeyCode::KeyY, "Y"),
+    (KeyCode::KeyZ, "Z"),
+    (KeyCode::Digit0, "0"),
+    (KeyCode::Digit1, "1"),
+    (KeyCode::Digit2, "2"),
+    (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"),
+    (KeyCode::Digit5, "5"),
+    (KeyCode::Digit6, "6"),
+    (KeyCode::Digit7, "7"),
+    (KeyCode::Digit8, "8"),
+    (KeyCode::Digit9, "9"),
+];
+
+/// Mouse buttons that can be bound
+const MOUSE_NAMES: [(MouseButton, &str); 3] = [
+    (MouseButton::Left, "Mouse Left"),
+    (MouseButton::Right, "Mouse Right"),
+    (MouseButton::Middle, "Mouse Middle"),
+];
+
+/// On-screen touch controls
+const MOBILE_NAMES: [(MobileButton, &str); 7] = [
+    (MobileButton::Left, "Touch Left"),
+    (MobileButton::Right, "Touch Right"),
+    (MobileButton::Rotate, "Touch Rotate"),
+    (MobileButton::SoftDrop, "Touch Soft Drop"),
+    (MobileButton::HardDrop, "Touch Hard Drop"),
+    (MobileButton::Hold, "Touch Hold"),
+    (MobileButton::Pause, "Touch Pause"),
+];
+
+impl Binding {
+    /// Name shown on the controls screen and written to storage
+    pub fn name(self) -> &'static str {
+        let name = match self {
+            Binding::Key(key) => KEY_NAMES.iter().find(|(k, _)| *k == key).map(|(_, n)| *n),
+            Binding::Mouse(button) => MOUSE_NAMES
+                .iter()
+                .find(|(b, _)| *b == button)
+                .map(|(_, n)| *n),
+            Binding::Mobile(button) => MOBILE_NAMES
+                .iter()
+                .find(|(b, _)| *b == button)
+                .map(|(_, n)| *n),
+        };
+        name.unwrap_or("?")
+    }
+
+    /// Look a binding up by its stored name
+    pub fn from_name(name: &str) -> Option<Binding> {
+        let key = KEY_NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|&(key, _)| Binding::Key(key));
+        let mouse = || {
+            MOUSE_NAMES
+                .iter()
+                .find(|(_, n)| *n == name)
+                .map(|&(button, _)| Binding::Mouse(button))
+        };
+        let mobile = || {
+            MOBILE_NAMES
+                .iter()
+                .find(|(_, n)| *n == name)
+                .map(|&(button, _)| Binding::Mobile(button))
+        };
+        key.or_else(mouse).or_else(mobile)
+    }
+
+    /// The first key or mouse button pressed this frame (for capturing a new binding)
+    pub fn pressed_this_frame(input: &Input) -> Option<Binding> {
+        let key = KEY_NAMES
+            .iter()
+            .find(|&&(key, _)| input.key_pressed(key))
+            .map(|&(key, _)| Binding::Key(key));
+        key.or_else(|| {
+            MOUSE_NAMES
+                .iter()
+                .find(|&&(button, _)| input.mouse_pressed(button))
+                .map(|&(button, _)| Binding::Mouse(button))
+        })
+    }
+
+    /// Whether this control went down this frame
+    /// Touch controls report their own state (edges for taps, held for the d-pad and soft drop)
+    fn pressed(self, input: &Input, mobile: Option<&TetrisMobileController>) -> bool {
+        match self {
+            Binding::Key(key) => input.key_pressed(key),
+            Binding::Mouse(button) => input.mouse_pressed(button),
+            Binding::Mobile(button) => mobile.is_some_and(|m| m.is_active(button)),
+        }
+    }
+
+    /// Whether this control is down right now
+    fn held(self, input: &Input, mobile: Option<&TetrisMobileController>) -> bool {
+        match self {
+            Binding::Key(key) => input.key_pressed(key) || input.key_held(key),
+            Binding::Mouse(button) => input.mouse_pressed(button) || input.mouse_held(button),
+            Binding::Mobile(button) => mobile.is_some_and(|m| m.is_active(button)),
+        }
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> String {
+        binding.name().to_string()
+    }
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Binding::from_name(&name).ok_or_else(|| format!("Unknown binding: {}", name))
+    }
+}
+
+/// Which controls trigger which actions
+/// Saved through `Storage`; actions missing from a save keep their defaults
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlBindings {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for ControlBindings {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .map(|&action| (action, action.default_bindings()))
+                .collect(),
+        }
+    }
+}
+
+impl ControlBindings {
+    /// Fill in actions that a save from an older version didn't know about
+    pub fn with_missing_defaults(mut self) -> Self {
+        for action in Action::ALL {
+            self.bindings
+                .entry(action)
+                .or_insert_with(|| action.default_bindings());
+        }
+        self
+    }
+
+    /// Controls bound to an action
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether any control bound to the action went down this frame
+    pub fn pressed(
+        &self,
+        action: Action,
+        input: &Input,
+        mobile: Option<&TetrisMobileController>,
+    ) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.pressed(input, mobile))
+    }
+
+    /// Whether any control bound to the action is down right now
+    pub fn held(
+        &self,
+        action: Action,
+        input: &Input,
+        mobile: Option<&TetrisMobileController>,
+    ) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.held(input, mobile))
+    }
+
+    /// Replace an action's keyboard and mouse bindings with a single control
+    /// Touch bindings stay as they are, and the control is taken away from any other action
+    /// read on the same screen (see `ActionContext`).
+    /// An action that would be left without a key or mouse button gets one of the replaced
+    /// controls instead (a swap); returns false and changes nothing if there aren't enough
+    pub fn rebind(&mut self, action: Action, binding: Binding) -> bool {
+        let is_key_or_mouse = |b: &Binding| !matches!(b, Binding::Mobile(_));
+        let mut freed: Vec<Binding> = self
+            .bindings(action)
+            .iter()
+            .copied()
+            .filter(|b| is_key_or_mouse(b) && *b != binding)
+            .collect();
+
+        // Other actions whose only key or mouse control is the one being taken
+        let stranded: Vec<Action> = Action::ALL
+            .iter()
+            .copied()
+            .filter(|&other| other != action && other.context() == action.context())
+            .filter(|&other| {
+                let mut controls = self.bindings(other).iter().filter(|b| is_key_or_mouse(b));
+                controls.next() == Some(&binding) && controls.next().is_none()
+            })
+            .collect();
+        if stranded.len() > freed.len() {
+            return false;
+        }
+
+        for (other, bindings) in self.bindings.iter_mut() {
+            if other.context() == action.context() {
+                bindings.retain(|&b| b != binding);
+            }
+        }
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|b| matches!(b, Binding::Mobile(_)));
+        bindings.insert(0, binding);
+
+        for other in stranded {
+            let swapped = freed.remove(0);
+            println!("Moved {} to {}", swapped.name(), other.label());
+            self.bindings.entry(other).or_default().insert(0, swapped);
+        }
+        true
+    }
+
+    /// Short description of an action's first two keyboard and mouse bindings, e.g. "Up / X"
+    pub fn describe(&self, action: Action) -> String {
+        let names: Vec<&str> = self
+            .bindings(action)
+            .iter()
+            .filter(|b| !matches!(b, Binding::Mobile(_)))
+            .take(2)
+            .map(|b| b.name())
+            .collect();
+        if names.is_empty() {
+            "Unbound".to_string()
+        } else {
+            names.join(" / ")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(bindings: &ControlBindings, action: Action) -> Vec<Binding> {
+        bindings
+            .bindings(action)
+            .iter()
+            .copied()
+            .filter(|b| !matches!(b, Binding::Mobile(_)))
+            .collect()
+    }
+
+    #[test]
+    fn rebinding_start_leaves_confirm_bound() {
+        let mut bindings = ControlBindings::default();
+        let enter = Binding::Key(KeyCode::Enter);
+
+        assert!(bindings.rebind(Action::Start, Binding::Key(KeyCode::KeyS)));
+        assert!(bindings.rebind(Action::Start, enter));
+
+        assert_eq!(keys(&bindings, Action::Start), [enter]);
+        assert_eq!(
+            keys(&bindings, Action::Confirm),
+            [enter, Binding::Key(KeyCode::KeyY)]
+        );
+    }
+
+    #[test]
+    fn rebinding_pause_leaves_cancel_bound() {
+        let mut bindings = ControlBindings::default();
+        let escape = Binding::Key(KeyCode::Escape);
+
+        assert!(bindings.rebind(Action::Pause, escape));
+
+        assert_eq!(keys(&bindings, Action::Pause), [escape]);
+        assert_eq!(
+            keys(&bindings, Action::Cancel),
+            [escape, Binding::Key(KeyCode::KeyN)]
+        );
+    }
+
+    #[test]
+    fn taking_a_key_within_gameplay_swaps_one_back() {
+        let mut bindings = ControlBindings::default();
+        let space = Binding::Key(KeyCode::Space);
+
+        assert!(bindings.rebind(Action::Hold, space));
+
+        assert_eq!(keys(&bindings, Action::Hold), [space]);
+        assert_eq!(
+            keys(&bindings, Action::HardDrop),
+            [Binding::Key(KeyCode::KeyC)]
+        );
+    }
+}
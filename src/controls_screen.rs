@@ -0,0 +1,199 @@
+use crate::controls::{Action, Binding, ControlBindings};
+use crate::coordinate_system::CoordinateSystem;
+use crate::retris_colors::*;
+use crate::retris_ui::Button;
+use crate::storage::Storage;
+use egor::input::{Input, KeyCode};
+use egor::math::vec2;
+use egor::render::Graphics;
+
+/// Lists every action with its bindings; click one, then press a key or mouse button to rebind it
+/// Changes are saved as soon as they're made
+pub struct ControlsScreen {
+    action_buttons: Vec<(Action, Button)>,
+    reset_button: Button,
+    back_button: Button,
+    bindings: ControlBindings,
+    capturing: Option<Action>, // Action waiting for its new key
+}
+
+impl ControlsScreen {
+    /// Scale factor based on screen height, clamped to prevent extreme sizes
+    fn scale_factor(screen_height: f32) -> f32 {
+        (screen_height / 1048.0).clamp(0.5, 2.0)
+    }
+
+    /// Base action row width (normalized to 1048px height)
+    const BASE_ROW_WIDTH: f32 = 440.0;
+    /// Base action row height (normalized to 1048px height)
+    const BASE_ROW_HEIGHT: f32 = 36.0;
+    /// Base spacing between action rows (normalized to 1048px height)
+    const BASE_ROW_SPACING: f32 = 8.0;
+    /// Base Y position of the first action row (normalized to 1048px height)
+    const BASE_FIRST_ROW_Y: f32 = -380.0;
+    /// Base Y position of the Reset / Back buttons (normalized to 1048px height)
+    const BASE_BUTTONS_Y: f32 = 270.0;
+    /// Base button height (normalized to 1048px height)
+    const BASE_BUTTON_HEIGHT: f32 = 50.0;
+
+    pub fn new() -> Self {
+        let mut screen = Self {
+            action_buttons: Action::ALL
+                .iter()
+                .map(|&action| (action, Button::new(0.0, 0.0, 0.0, 0.0, action.label())))
+                .collect(),
+            reset_button: Button::new(0.0, 0.0, 0.0, 0.0, "Reset"),
+            back_button: Button::new(0.0, 0.0, 0.0, 0.0, "Back"),
+            bindings: Storage::load_control_bindings(),
+            capturing: None,
+        };
+        // Use default screen dimensions for initial layout (will be updated via update)
+        screen.update(640.0, 1048.0);
+        screen.refresh_labels();
+        screen
+    }
+
+    /// Reload the bindings when the screen is opened
+    pub fn open(&mut self) {
+        self.bindings = Storage::load_control_bindings();
+        self.capturing = None;
+        self.refresh_labels();
+    }
+
+    /// Update button positions and sizes based on actual screen dimensions
+    pub fn update(&mut self, _screen_width: f32, screen_height: f32) {
+        let scale = Self::scale_factor(screen_height);
+        let width = Self::BASE_ROW_WIDTH * scale;
+        let height = Self::BASE_ROW_HEIGHT * scale;
+        let step = height + Self::BASE_ROW_SPACING * scale;
+        let left_x = -width / 2.0;
+        let first_y = Self::BASE_FIRST_ROW_Y * scale;
+
+        for (index, (_, button)) in self.action_buttons.iter_mut().enumerate() {
+            button.set_position(left_x, first_y + step * index as f32, width, height);
+        }
+
+        // Reset and Back side by side under the list
+        let button_height = Self::BASE_BUTTON_HEIGHT * scale;
+        let half_width = width / 2.0 - Self::BASE_ROW_SPACING * scale;
+        let buttons_y = Self::BASE_BUTTONS_Y * scale;
+        self.reset_button
+            .set_position(left_x, buttons_y, half_width, button_height);
+        self.back_button.set_position(
+            left_x + width - half_width,
+            buttons_y,
+            half_width,
+            button_height,
+        );
+    }
+
+    /// Handle input for the controls screen
+    /// Returns true when the player leaves the screen
+    pub fn handle_input(&mut self, input: &Input, screen_width: f32, screen_height: f32) -> bool {
+        if let Some(action) = self.capturing {
+            // Escape backs out instead of being bound
+            if input.key_pressed(KeyCode::Escape) {
+                self.capturing = None;
+                self.refresh_labels();
+            } else if let Some(binding) = Binding::pressed_this_frame(input) {
+                if self.bindings.rebind(action, binding) {
+                    Storage::save_control_bindings(&self.bindings);
+                    println!("Bound {} to {}", action.label(), binding.name());
+                } else {
+                    println!(
+                        "Can't bind {} to {}: another action would be left without a key",
+                        action.label(),
+                        binding.name()
+                    );
+                }
+                self.capturing = None;
+                self.refresh_labels();
+            }
+            return false;
+        }
+
+        if let Some(&(action, _)) = self
+            .action_buttons
+            .iter()
+            .find(|(_, button)| button.is_clicked(input, screen_width, screen_height))
+        {
+            self.capturing = Some(action);
+            self.refresh_labels();
+            return false;
+        }
+
+        if self
+            .reset_button
+            .is_clicked(input, screen_width, screen_height)
+        {
+            self.bindings = ControlBindings::default();
+            Storage::save_control_bindings(&self.bindings);
+            self.refresh_labels();
+            return false;
+        }
+
+        input.key_pressed(KeyCode::Escape)
+            || self
+                .back_button
+                .is_clicked(input, screen_width, screen_height)
+    }
+
+    /// Show each action's current bindings on its button
+    fn refresh_labels(&mut self) {
+        for (action, button) in self.action_buttons.iter_mut() {
+            let label = if self.capturing == Some(*action) {
+                format!("{}: press a key...", action.label())
+            } else {
+                format!("{}: {}", action.label(), self.bindings.describe(*action))
+            };
+            button.set_label(&label);
+        }
+    }
+
+    pub fn draw(&self, gfx: &mut Graphics, screen_width: f32, screen_height: f32) {
+        let coords = CoordinateSystem::with_default_offset(screen_width, screen_height);
+        let scale = Self::scale_factor(screen_height);
+
+        // Same backdrop as the settings screen
+        gfx.rect()
+            .at(coords.top_left_world())
+            .size(vec2(screen_width, screen_height))
+            .color(COLOR_DARK_GRAY);
+
+        // Title
+        let title_text = "CONTROLS";
+        let title_size = (screen_height * 0.046).clamp(32.0, 80.0);
+        let title_world_x = coords.center_text_x(title_text, title_size, 0.5);
+        let title_screen_pos = coords.world_to_screen(vec2(title_world_x, -460.0 * scale));
+        gfx.text(title_text)
+            .at(title_screen_pos)
+            .size(title_size)
+            .color(COLOR_TEXT_GREEN);
+
+        for (_, button) in &self.action_buttons {
+            button.draw(gfx, screen_width, screen_height);
+        }
+        self.reset_button.draw(gfx, screen_width, screen_height);
+        self.back_button.draw(gfx, screen_width, screen_height);
+
+        // Hint under the buttons
+        let hint = if self.capturing.is_some() {
+            "Press a key or mouse button - Esc to cancel"
+        } else {
+            "Click an action to change its key"
+        };
+        let hint_size = (screen_height * 0.018).clamp(12.0, 36.0);
+        let hint_world_x = coords.center_text_x(hint, hint_size, 0.5);
+        let hint_screen_pos = coords.world_to_screen(vec2(hint_world_x, 350.0 * scale));
+        gfx.text(hint)
+            .at(hint_screen_pos)
+            .size(hint_size)
+            .color(COLOR_TEXT_GREEN);
+    }
+}
+
+impl Default for ControlsScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,1143 @@
+use crate::board::Board;
+use crate::game_data::{ClearInfo, ScoreManager, TSpin};
+use crate::garbage::GarbageGenerator;
+use crate::gravity::{FRAMES_PER_SECOND, GravityTable, TWENTY_G};
+use crate::handling::HandlingProfile;
+use crate::randomizer::{GameRng, Randomizer, seeded_rng};
+use crate::rotation::{ORIENTATION_COUNT, PieceCells, RotationSystem};
+use crate::ruleset::{Goal, LevelUpEffect, MAX_PREVIEW_COUNT, MIN_PREVIEW_COUNT, Ruleset};
+use crate::tetris_shape::SHAPE_TEE;
+use std::collections::VecDeque;
+
+// ============================================================================
+// Headless game rules
+// ============================================================================
+// The engine knows nothing about windows, pixels or audio. Each tick it takes
+// a `TickInput` (what the player is doing) and returns `GameEvent`s (what
+// happened). `Game` drives it from egor input and turns the events into
+// sounds and callouts; tests and bots can drive it directly.
+// ============================================================================
+
+/// Simulation steps per second - every tick advances the game by exactly this much time
+pub const TICKS_PER_SECOND: f32 = FRAMES_PER_SECOND;
+
+/// Length of one simulation step (in seconds)
+pub const TICK_DURATION: f32 = 1.0 / TICKS_PER_SECOND;
+
+/// Board width in cells
+pub const BOARD_WIDTH: usize = 10;
+
+/// Board height in cells (visible playfield)
+pub const BOARD_HEIGHT: usize = 20;
+
+/// Default time a grounded piece waits before locking (seconds)
+pub const DEFAULT_LOCK_DELAY: f32 = 0.5;
+
+/// Default number of times moving or rotating can restart the lock delay
+pub const DEFAULT_MAX_LOCK_RESETS: u32 = 15;
+
+/// Row index where new pieces spawn (in the spawn area above the visible grid)
+/// One row down from the top so pieces that extend above their pivot still fit
+const SPAWN_ROW: i32 = 1;
+
+/// How long play stops while the board is wiped on level up (in seconds)
+const LEVEL_TRANSITION_DURATION: f32 = 1.5;
+
+/// What the player is doing during one tick
+/// Held inputs stay true for as long as the button is down, pressed inputs only on the tick
+/// they were pressed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TickInput {
+    /// Held: move left (with DAS)
+    pub left: bool,
+    /// Held: move right (with DAS)
+    pub right: bool,
+    /// Held: soft drop
+    pub soft_drop: bool,
+    /// Pressed: hard drop
+    pub hard_drop: bool,
+    /// Pressed: rotate clockwise
+    pub rotate_cw: bool,
+    /// Pressed: rotate counter-clockwise
+    pub rotate_ccw: bool,
+    /// Pressed: rotate 180 degrees
+    pub rotate_180: bool,
+    /// Pressed: swap with the hold slot
+    pub hold: bool,
+}
+
+impl TickInput {
+    /// Combine two inputs, keeping anything either of them had
+    /// Used to keep presses that happen between ticks
+    pub fn merged(self, other: TickInput) -> TickInput {
+        TickInput {
+            left: self.left || other.left,
+            right: self.right || other.right,
+            soft_drop: self.soft_drop || other.soft_drop,
+            hard_drop: self.hard_drop || other.hard_drop,
+            rotate_cw: self.rotate_cw || other.rotate_cw,
+            rotate_ccw: self.rotate_ccw || other.rotate_ccw,
+            rotate_180: self.rotate_180 || other.rotate_180,
+            hold: self.hold || other.hold,
+        }
+    }
+
+    /// The same input with the one-tick presses dropped (for the following ticks)
+    pub fn held_only(self) -> TickInput {
+        TickInput {
+            left: self.left,
+            right: self.right,
+            soft_drop: self.soft_drop,
+            ..TickInput::default()
+        }
+    }
+}
+
+/// Something that happened during a tick
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// A new piece entered the board
+    PieceSpawned { shape_index: i32 },
+    /// The active piece rotated by this many clockwise quarter turns
+    Rotated { quarter_turns: usize },
+    /// The active piece went into the hold slot
+    Held,
+    /// The active piece locked into the board (hard_drop_rows is 0 if it wasn't hard dropped)
+    PieceLocked { hard_drop_rows: u32 },
+    /// Lines were cleared, or a T-spin locked without clearing any
+    Cleared {
+        clear: ClearInfo,
+        points: u64,
+        back_to_back: u32,
+    },
+    /// The level went up; `wiped_cells` holds the board's cells if it was wiped (x, y, shape)
+    LevelUp {
+        level: u16,
+        wiped_cells: Option<Vec<(i32, i32, i32)>>,
+    },
+    /// The wipe pause after a level up is over and pieces start falling again
+    LevelTransitionFinished,
+    /// The stack reached the spawn area
+    GameOver,
+    /// The ruleset's goal was met and the game is finished
+    GoalReached,
+    /// The ruleset's time limit ran out
+    TimeUp,
+    /// A new garbage row pushed the stack up
+    GarbageAdded,
+    /// A piece locked without clearing lines, ending a combo
+    ComboBroken,
+}
+
+/// Why a game finished
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEnd {
+    /// The stack reached the spawn area
+    ToppedOut,
+    /// The ruleset's goal was met
+    GoalReached,
+    /// The ruleset's time limit ran out
+    TimeUp,
+}
+
+/// The piece the player is controlling, in grid cells
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivePiece {
+    pub shape_index: i32,
+    pub orientation: usize, // Current rotation state (0 = spawn, 1 = right, 2 = 180, 3 = left)
+    pub cell_x: i32,        // Grid cell X position of the pivot
+    pub cell_y: i32,        // Grid cell Y position of the pivot
+    offsets: PieceCells,    // Cells relative to the pivot in the current orientation
+    fall_progress: f32,     // Fraction of a cell fallen towards the next row
+    lock_timer: f32,        // Time spent grounded since the last reset
+    lock_resets: u32,       // Resets used since the piece last reached a new lowest row
+    lowest_cell_y: i32,     // Lowest row reached so far (falling further refunds resets)
+    last_move_was_rotation: bool, // True if the last successful move was a rotation (T-spins)
+    last_kick_was_full_t_spin: bool, // True if the last rotation used a kick that forces a full T-spin
+}
+
+impl ActivePiece {
+    fn new(
+        shape_index: i32,
+        rotation_system: &dyn RotationSystem,
+        cell_x: i32,
+        cell_y: i32,
+    ) -> Self {
+        Self {
+            shape_index,
+            orientation: 0,
+            cell_x,
+            cell_y,
+            offsets: rotation_system.cells(shape_index, 0),
+            fall_progress: 0.0,
+            lock_timer: 0.0,
+            lock_resets: 0,
+            lowest_cell_y: cell_y,
+            last_move_was_rotation: false,
+            last_kick_was_full_t_spin: false,
+        }
+    }
+
+    /// The piece's cells in grid coordinates
+    pub fn cells(&self) -> PieceCells {
+        self.cells_at(self.cell_x, self.cell_y)
+    }
+
+    fn cells_at(&self, cell_x: i32, cell_y: i32) -> PieceCells {
+        self.offsets.map(|(x, y)| (cell_x + x, cell_y + y))
+    }
+
+    /// Find the row the piece would land on if dropped straight down
+    /// Returns the cell_y of the landing position (the ghost piece position)
+    pub fn ghost_cell_y(&self, board: &Board) -> i32 {
+        let mut cells = self.cells();
+        let mut drop_distance = 0;
+
+        while board.can_move_down(&cells) {
+            for cell in cells.iter_mut() {
+                cell.1 += 1;
+            }
+            drop_distance += 1;
+        }
+
+        self.cell_y + drop_distance
+    }
+
+    /// Move one cell left (-1) or right (1) if nothing is in the way
+    fn shift(&mut self, direction: i32, board: &Board) -> bool {
+        if !board.fits(&self.cells_at(self.cell_x + direction, self.cell_y)) {
+            return false;
+        }
+        self.cell_x += direction;
+        self.last_move_was_rotation = false;
+        true
+    }
+
+    /// Rotate by a number of clockwise quarter turns, trying the rotation system's kicks
+    /// Returns false (leaving the piece untouched) if no kick offset fits
+    fn rotate(
+        &mut self,
+        rotation_system: &dyn RotationSystem,
+        quarter_turns: usize,
+        board: &Board,
+    ) -> bool {
+        let from = self.orientation;
+        let to = (from + quarter_turns) % ORIENTATION_COUNT;
+        let offsets = rotation_system.cells(self.shape_index, to);
+
+        let kicks = rotation_system.kicks(self.shape_index, from, to);
+        for (kick_index, &(kick_x, kick_y)) in kicks.iter().enumerate() {
+            let test_x = self.cell_x + kick_x;
+            let test_y = self.cell_y + kick_y;
+            let cells = offsets.map(|(x, y)| (test_x + x, test_y + y));
+            if board.fits(&cells) {
+                self.offsets = offsets;
+                self.cell_x = test_x;
+                self.cell_y = test_y;
+                self.orientation = to;
+                self.last_move_was_rotation = true;
+                self.last_kick_was_full_t_spin =
+                    rotation_system.is_full_t_spin_kick(self.shape_index, from, to, kick_index);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Move down one row, refunding lock resets when reaching a new lowest row
+    fn fall_one_row(&mut self) {
+        self.cell_y += 1;
+        self.last_move_was_rotation = false;
+        self.reached_row(self.cell_y);
+    }
+
+    fn reached_row(&mut self, cell_y: i32) {
+        if cell_y > self.lowest_cell_y {
+            self.lowest_cell_y = cell_y;
+            self.lock_resets = 0;
+        }
+    }
+
+    /// Check whether the piece is locking as a T-spin, using the 3-corner rule
+    /// The last move must have been a rotation and 3 of the 4 corners around the T's center
+    /// must be filled (walls and floor count). With only one of the two corners the T points
+    /// at filled it's a mini, unless the rotation system says the kick it used counts as full
+    fn detect_t_spin(&self, board: &Board) -> TSpin {
+        const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+        if self.shape_index != SHAPE_TEE || !self.last_move_was_rotation {
+            return TSpin::None;
+        }
+
+        // The center is the cell touching the other three; the T points away from its missing arm
+        let cells = self.cells();
+        let is_neighbor = |a: (i32, i32), b: (i32, i32)| (a.0 - b.0).abs() + (a.1 - b.1).abs() == 1;
+        let Some(&(center_x, center_y)) = cells.iter().find(|&&cell| {
+            cells
+                .iter()
+                .filter(|&&other| is_neighbor(cell, other))
+                .count()
+                == 3
+        }) else {
+            return TSpin::None;
+        };
+        let Some(&(back_x, back_y)) = DIRECTIONS
+            .iter()
+            .find(|&&(dx, dy)| !cells.contains(&(center_x + dx, center_y + dy)))
+        else {
+            return TSpin::None;
+        };
+        let (front_x, front_y) = (-back_x, -back_y);
+        let (side_x, side_y) = (-front_y, front_x);
+
+        let corner_filled =
+            |dx: i32, dy: i32| board.is_cell_occupied(center_x + dx, center_y + dy) as usize;
+        let front_corners = corner_filled(front_x + side_x, front_y + side_y)
+            + corner_filled(front_x - side_x, front_y - side_y);
+        let back_corners = corner_filled(back_x + side_x, back_y + side_y)
+            + corner_filled(back_x - side_x, back_y - side_y);
+
+        if front_corners + back_corners < 3 {
+            TSpin::None
+        } else if front_corners == 2 || self.last_kick_was_full_t_spin {
+            TSpin::Full
+        } else {
+            TSpin::Mini
+        }
+    }
+}
+
+/// Delayed auto shift: the first press moves at once, holding repeats after a delay
+/// Lives in the engine rather than the piece, so a charged DAS carries over to the next piece
+#[derive(Debug, Clone, Copy, Default)]
+struct AutoShift {
+    direction: i32,  // Direction being held (-1, 0, 1)
+    das_timer: f32,  // Time held before auto-repeat starts
+    active: bool,    // Whether auto-repeat has started
+    move_timer: f32, // Accumulator for auto-repeat moves
+}
+
+impl AutoShift {
+    /// Apply the held direction to the piece, returning true if it moved
+    fn update(
+        &mut self,
+        direction: i32,
+        handling: &HandlingProfile,
+        piece: &mut ActivePiece,
+        board: &Board,
+    ) -> bool {
+        if direction == 0 {
+            // Nothing held (or both) - reset DAS
+            *self = AutoShift::default();
+            return false;
+        }
+
+        if direction != self.direction {
+            // Direction changed - reset DAS and move immediately
+            *self = AutoShift {
+                direction,
+                ..AutoShift::default()
+            };
+            return piece.shift(direction, board);
+        }
+
+        if !self.active {
+            // In DAS delay phase
+            self.das_timer += TICK_DURATION;
+            if self.das_timer < handling.das() {
+                return false;
+            }
+            self.active = true;
+            self.move_timer = 0.0;
+        }
+
+        let time_per_cell = handling.arr();
+        let mut moved = false;
+        if time_per_cell <= 0.0 {
+            // ARR 0 - straight to the wall
+            while piece.shift(direction, board) {
+                moved = true;
+            }
+            return moved;
+        }
+
+        // DAS active - continuous movement at ARR speed
+        self.move_timer += TICK_DURATION;
+        while self.move_timer >= time_per_cell {
+            if piece.shift(direction, board) {
+                self.move_timer -= time_per_cell;
+                moved = true;
+            } else {
+                // Hit wall - keep DAS active but stop moving
+                self.move_timer = 0.0;
+                break;
+            }
+        }
+        moved
+    }
+}
+
+/// The complete rules of a game: board, active piece, queue, hold, scoring and randomizer
+pub struct Engine {
+    board: Board,
+    active_piece: Option<ActivePiece>,
+    randomizer: Box<dyn Randomizer>,
+    rotation_system: Box<dyn RotationSystem>,
+    gravity: GravityTable,
+    handling: HandlingProfile,
+    lock_delay: f32,
+    max_lock_resets: u32,
+    level_up_effect: LevelUpEffect,
+    goal: Goal,
+    garbage: Option<GarbageGenerator>, // Adds new garbage rows over time (dig)
+    garbage_cleared: u16,              // Garbage lines cleared so far
+    next_queue: VecDeque<i32>,         // Upcoming pieces, front is spawned next
+    preview_count: usize,
+    held_piece: Option<i32>, // Shape index of the piece in the hold slot
+    hold_used: bool,         // True once hold was used for the current piece
+    auto_shift: AutoShift,
+    rng: GameRng,
+    seed: u64,
+    score_manager: ScoreManager,
+    level_transition: Option<f32>, // Time spent in the level up wipe pause
+    ticks: u64,
+    end: Option<GameEnd>,
+}
+
+impl Engine {
+    /// Create a new game
+    /// Passing a seed reproduces that exact run; `None` picks a fresh random seed
+    pub fn new(ruleset: &Ruleset, handling: HandlingProfile, seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(rand::random);
+
+        let mut randomizer = ruleset.randomizer.create();
+        let mut rng = seeded_rng(seed);
+        let preview_count = ruleset
+            .preview_count
+            .clamp(MIN_PREVIEW_COUNT, MAX_PREVIEW_COUNT);
+        let next_queue = (0..preview_count)
+            .map(|_| randomizer.next_piece(&mut rng))
+            .collect();
+
+        let mut board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
+        let garbage = ruleset.garbage.map(|settings| {
+            let mut garbage = GarbageGenerator::new(settings);
+            garbage.fill(&mut board, &mut rng);
+            garbage
+        });
+
+        Self {
+            board,
+            active_piece: None,
+            randomizer,
+            rotation_system: ruleset.rotation_system.create(),
+            gravity: ruleset.gravity.table(),
+            handling: handling.clamped(),
+            lock_delay: ruleset.lock_delay,
+            max_lock_resets: ruleset.max_lock_resets,
+            level_up_effect: ruleset.level_up_effect,
+            goal: ruleset.goal,
+            garbage,
+            garbage_cleared: 0,
+            next_queue,
+            preview_count,
+            held_piece: None,
+            hold_used: false,
+            auto_shift: AutoShift::default(),
+            rng,
+            seed,
+            score_manager: ScoreManager::new(ruleset.scoring_rule),
+            level_transition: None,
+            ticks: 0,
+            end: None,
+        }
+    }
+
+    /// Advance the game by one tick (TICK_DURATION seconds)
+    pub fn tick(&mut self, input: &TickInput) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if self.end.is_some() {
+            return events;
+        }
+        if self.time_left_millis() == Some(0) {
+            self.end = Some(GameEnd::TimeUp);
+            events.push(GameEvent::TimeUp);
+            return events;
+        }
+        self.ticks += 1;
+
+        // Play is stopped while the board is being wiped after a level up
+        if let Some(elapsed) = self.level_transition {
+            let elapsed = elapsed + TICK_DURATION;
+            if elapsed >= LEVEL_TRANSITION_DURATION {
+                self.level_transition = None;
+                events.push(GameEvent::LevelTransitionFinished);
+            } else {
+                self.level_transition = Some(elapsed);
+            }
+            return events;
+        }
+
+        // New garbage pushes the stack (and the falling piece, if it's in the way) up
+        let garbage_added = match self.garbage.as_mut() {
+            Some(garbage) => garbage.tick(&mut self.board, &mut self.rng),
+            None => false,
+        };
+        if garbage_added {
+            let board = &self.board;
+            let blocked_piece = self
+                .active_piece
+                .as_mut()
+                .filter(|piece| !board.fits(&piece.cells()));
+            if let Some(piece) = blocked_piece {
+                piece.cell_y -= 1;
+            }
+            events.push(GameEvent::GarbageAdded);
+        }
+
+        // Check for game over condition (blocks in spawn area)
+        if self.board.has_blocks_in_spawn_area() {
+            self.end = Some(GameEnd::ToppedOut);
+            events.push(GameEvent::GameOver);
+            return events;
+        }
+
+        if self.active_piece.is_none() {
+            self.spawn_next_piece(&mut events);
+        }
+
+        let locked = self.update_active_piece(input, &mut events);
+
+        match locked {
+            Some(hard_drop_rows) => self.lock_active_piece(hard_drop_rows, &mut events),
+            None => {
+                if input.hold {
+                    self.hold_active_piece(&mut events);
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Move, rotate and drop the active piece
+    /// Returns Some(hard drop rows) if the piece locked this tick
+    fn update_active_piece(
+        &mut self,
+        input: &TickInput,
+        events: &mut Vec<GameEvent>,
+    ) -> Option<u32> {
+        let rotation_system = self.rotation_system.as_ref();
+        let board = &self.board;
+        let piece = self.active_piece.as_mut()?;
+
+        // Tracks successful moves and rotations, which restart the lock delay
+        let mut moved = false;
+
+        for (pressed, quarter_turns) in [
+            (input.rotate_cw, 1),
+            (input.rotate_ccw, 3),
+            (input.rotate_180, 2),
+        ] {
+            if pressed && piece.rotate(rotation_system, quarter_turns, board) {
+                events.push(GameEvent::Rotated { quarter_turns });
+                moved = true;
+            }
+        }
+
+        // Horizontal movement: if both directions are held, don't move
+        let direction = match (input.left, input.right) {
+            (true, false) => -1,
+            (false, true) => 1,
+            _ => 0,
+        };
+        moved |= self
+            .auto_shift
+            .update(direction, &self.handling, piece, board);
+
+        // Hard drop: move straight to the landing row and lock immediately
+        if input.hard_drop {
+            let landing_cell_y = piece.ghost_cell_y(board);
+            let hard_drop_rows = (landing_cell_y - piece.cell_y) as u32;
+            if hard_drop_rows > 0 {
+                piece.last_move_was_rotation = false;
+            }
+            piece.cell_y = landing_cell_y;
+            return Some(hard_drop_rows);
+        }
+
+        // Gravity in cells per frame, multiplied while soft dropping
+        let gravity = self.gravity.cells_per_frame(self.score_manager.level());
+        let cells_per_frame = if input.soft_drop {
+            gravity * self.handling.soft_drop_factor as f32
+        } else {
+            gravity
+        };
+        if cells_per_frame >= TWENTY_G {
+            // 20G: straight to the floor, the lock delay still applies there
+            let landing_cell_y = piece.ghost_cell_y(board);
+            if landing_cell_y > piece.cell_y {
+                piece.cell_y = landing_cell_y;
+                piece.last_move_was_rotation = false;
+                piece.reached_row(landing_cell_y);
+            }
+            piece.fall_progress = 0.0;
+        } else {
+            piece.fall_progress += cells_per_frame * TICK_DURATION * FRAMES_PER_SECOND;
+        }
+        while piece.fall_progress >= 1.0 {
+            if !board.can_move_down(&piece.cells()) {
+                // Resting on the stack - the lock delay below decides when it locks
+                piece.fall_progress = 0.0;
+                break;
+            }
+            piece.fall_one_row();
+            piece.fall_progress -= 1.0;
+        }
+
+        // Lock delay: a grounded piece can still slide and spin until the timer expires
+        if board.can_move_down(&piece.cells()) {
+            // Lifted off the stack (slid over an edge) - timer starts over when it lands again
+            piece.lock_timer = 0.0;
+            return None;
+        }
+        if moved && piece.lock_resets < self.max_lock_resets {
+            piece.lock_timer = 0.0;
+            piece.lock_resets += 1;
+        }
+        piece.lock_timer += TICK_DURATION;
+        (piece.lock_timer >= self.lock_delay).then_some(0)
+    }
+
+    /// Put the active piece into the board, clear lines and score them
+    fn lock_active_piece(&mut self, hard_drop_rows: u32, events: &mut Vec<GameEvent>) {
+        let Some(piece) = self.active_piece.take() else {
+            return;
+        };
+
+        // Hard drops earn drop points
+        if hard_drop_rows > 0 {
+            self.score_manager.on_hard_drop(hard_drop_rows);
+        }
+        events.push(GameEvent::PieceLocked { hard_drop_rows });
+
+        // Holding becomes available again for the next piece
+        self.hold_used = false;
+
+        // T-spins are judged against the stack before the piece joins it
+        let t_spin = piece.detect_t_spin(&self.board);
+        self.board.lock_cells(&piece.cells(), piece.shape_index);
+
+        // Clear completed lines and update score
+        self.garbage_cleared += self.board.count_full_garbage_rows() as u16;
+        let lines_cleared = self.board.clear_completed_lines();
+        let clear = ClearInfo {
+            rows: lines_cleared as u16,
+            t_spin,
+            perfect_clear: lines_cleared > 0 && self.board.is_empty(),
+        };
+
+        if lines_cleared == 0 {
+            // A T-spin without lines still scores
+            if t_spin != TSpin::None {
+                let points = self.score_manager.on_rows_cleared(clear);
+                events.push(GameEvent::Cleared {
+                    clear,
+                    points,
+                    back_to_back: 0,
+                });
+            }
+
+            // Piece landed without clearing lines - reset multiplier
+            if self.score_manager.combo_count() > 0 {
+                events.push(GameEvent::ComboBroken);
+            }
+            self.score_manager.on_piece_landed_no_clear();
+            return;
+        }
+
+        let old_level = self.score_manager.level();
+        let points = self.score_manager.on_rows_cleared(clear);
+        let back_to_back = self.score_manager.back_to_back_count();
+        events.push(GameEvent::Cleared {
+            clear,
+            points,
+            back_to_back,
+        });
+
+        let goal_reached = match self.goal {
+            Goal::Lines(lines) => self.score_manager.lines_cleared() >= lines,
+            Goal::GarbageLines(lines) => self.garbage_cleared >= lines,
+            Goal::Endless | Goal::TimeLimit(_) => false,
+        };
+        if goal_reached {
+            self.end = Some(GameEnd::GoalReached);
+            events.push(GameEvent::GoalReached);
+            return;
+        }
+
+        // Check for level up
+        let new_level = self.score_manager.level();
+        if new_level > old_level {
+            let wiped_cells = match self.level_up_effect {
+                LevelUpEffect::Wipe => {
+                    self.level_transition = Some(0.0);
+                    Some(self.board.wipe())
+                }
+                // Board stays as it is, play carries straight on
+                LevelUpEffect::Flash => None,
+            };
+            events.push(GameEvent::LevelUp {
+                level: new_level,
+                wiped_cells,
+            });
+        }
+    }
+
+    /// Move the active piece into the hold slot, bringing out the previously held piece
+    /// (or the next queued piece if the slot was empty)
+    /// Returns false if holding isn't allowed right now (only one hold per piece)
+    fn hold_active_piece(&mut self, events: &mut Vec<GameEvent>) -> bool {
+        if self.hold_used {
+            return false;
+        }
+        let Some(shape_index) = self.active_piece.as_ref().map(|piece| piece.shape_index) else {
+            return false;
+        };
+
+        // The held piece comes back fresh, in its spawn orientation
+        match self.held_piece.replace(shape_index) {
+            Some(held_index) => self.spawn_piece(held_index, events),
+            None => self.spawn_next_piece(events),
+        }
+        self.hold_used = true;
+        events.push(GameEvent::Held);
+        true
+    }
+
+    /// Spawn the next piece from the queue
+    fn spawn_next_piece(&mut self, events: &mut Vec<GameEvent>) {
+        // Take the front of the queue and top it back up from the randomizer
+        let shape_index = match self.next_queue.pop_front() {
+            Some(shape_index) => shape_index,
+            None => self.randomizer.next_piece(&mut self.rng),
+        };
+        while self.next_queue.len() < self.preview_count {
+            let upcoming = self.randomizer.next_piece(&mut self.rng);
+            self.next_queue.push_back(upcoming);
+        }
+
+        self.spawn_piece(shape_index, events);
+    }
+
+    /// Spawn a specific piece at the top of the board
+    fn spawn_piece(&mut self, shape_index: i32, events: &mut Vec<GameEvent>) {
+        // Pivot sits left of center so 3-wide pieces spawn in the middle columns (3-5 on a 10-wide grid)
+        let spawn_cell_x = (self.board.width() as i32 - 1) / 2;
+        self.active_piece = Some(ActivePiece::new(
+            shape_index,
+            self.rotation_system.as_ref(),
+            spawn_cell_x,
+            SPAWN_ROW,
+        ));
+        events.push(GameEvent::PieceSpawned { shape_index });
+    }
+
+    /// Change DAS, ARR and soft drop mid-game (a held direction keeps its charge)
+    pub fn set_handling(&mut self, handling: HandlingProfile) {
+        self.handling = handling.clamped();
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn active_piece(&self) -> Option<&ActivePiece> {
+        self.active_piece.as_ref()
+    }
+
+    /// Upcoming pieces, next one first
+    pub fn next_queue(&self) -> Vec<i32> {
+        self.next_queue.iter().copied().collect()
+    }
+
+    /// Shape index of the piece in the hold slot
+    pub fn held_piece(&self) -> Option<i32> {
+        self.held_piece
+    }
+
+    /// Whether the current piece may still be held
+    pub fn can_hold(&self) -> bool {
+        !self.hold_used
+    }
+
+    pub fn rotation_system(&self) -> &dyn RotationSystem {
+        self.rotation_system.as_ref()
+    }
+
+    pub fn score_manager(&self) -> &ScoreManager {
+        &self.score_manager
+    }
+
+    pub fn score_manager_mut(&mut self) -> &mut ScoreManager {
+        &mut self.score_manager
+    }
+
+    /// Get the seed driving this game's randomness (share it to replay the run)
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Number of ticks simulated so far
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Progress of the level up wipe pause (0.0 to 1.0), if one is running
+    pub fn level_transition_progress(&self) -> Option<f32> {
+        self.level_transition
+            .map(|elapsed| elapsed / LEVEL_TRANSITION_DURATION)
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.end.is_some()
+    }
+
+    /// Why the game finished (None while it's still going)
+    pub fn game_end(&self) -> Option<GameEnd> {
+        self.end
+    }
+
+    /// Game time so far in milliseconds, counted in ticks so pauses and frame drops don't count
+    pub fn elapsed_millis(&self) -> u64 {
+        self.ticks * 1000 / TICKS_PER_SECOND as u64
+    }
+
+    /// Time left before a time limit runs out (None without a time limit)
+    pub fn time_left_millis(&self) -> Option<u64> {
+        match self.goal {
+            Goal::TimeLimit(seconds) => {
+                Some((u64::from(seconds) * 1000).saturating_sub(self.elapsed_millis()))
+            }
+            Goal::Endless | Goal::Lines(_) | Goal::GarbageLines(_) => None,
+        }
+    }
+
+    /// Garbage lines cleared so far (dig)
+    pub fn garbage_cleared(&self) -> u16 {
+        self.garbage_cleared
+    }
+
+    pub fn goal(&self) -> Goal {
+        self.goal
+    }
+}
+
+/// Name of a T-spin clear for callouts, e.g. "T-SPIN DOUBLE" or "T-SPIN MINI SINGLE"
+pub fn t_spin_name(clear: ClearInfo) -> String {
+    let spin = match clear.t_spin {
+        TSpin::Mini => "T-SPIN MINI",
+        _ => "T-SPIN",
+    };
+    match clear.rows {
+        0 => spin.to_string(),
+        1 => format!("{} SINGLE", spin),
+        2 => format!("{} DOUBLE", spin),
+        _ => format!("{} TRIPLE", spin),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::SPAWN_ROWS;
+    use crate::tetris_shape::{SHAPE_SQUARE, SHAPE_STRAIGHT};
+
+    const SEED: u64 = 20240611;
+
+    /// Lowest row of the board (y grows downward)
+    const BOTTOM_ROW: i32 = (BOARD_HEIGHT + SPAWN_ROWS) as i32 - 1;
+
+    /// An engine with the default rules and its first piece already spawned
+    fn started_engine() -> Engine {
+        let mut engine = Engine::new(&Ruleset::default(), HandlingProfile::default(), Some(SEED));
+        engine.tick(&TickInput::default());
+        engine
+    }
+
+    /// Swap the active piece for a fresh one of the given shape, orientation and position
+    fn place_piece(
+        engine: &mut Engine,
+        shape_index: i32,
+        orientation: usize,
+        cell_x: i32,
+        cell_y: i32,
+    ) {
+        let rotation_system = engine.rotation_system.as_ref();
+        let mut piece = ActivePiece::new(shape_index, rotation_system, cell_x, cell_y);
+        piece.orientation = orientation;
+        piece.offsets = rotation_system.cells(shape_index, orientation);
+        assert!(
+            engine.board.fits(&piece.cells()),
+            "test piece overlaps the board"
+        );
+        engine.active_piece = Some(piece);
+    }
+
+    /// Fill a row of the board, leaving the given columns empty
+    fn fill_row(engine: &mut Engine, row: i32, holes: &[i32]) {
+        let cells: Vec<(i32, i32)> = (0..BOARD_WIDTH as i32)
+            .filter(|column| !holes.contains(column))
+            .map(|column| (column, row))
+            .collect();
+        engine.board.lock_cells(&cells, SHAPE_SQUARE);
+    }
+
+    fn piece(engine: &Engine) -> &ActivePiece {
+        engine
+            .active_piece
+            .as_ref()
+            .expect("a piece should be active")
+    }
+
+    fn hard_drop() -> TickInput {
+        TickInput {
+            hard_drop: true,
+            ..TickInput::default()
+        }
+    }
+
+    fn cleared(events: &[GameEvent]) -> Option<ClearInfo> {
+        events.iter().find_map(|event| match event {
+            GameEvent::Cleared { clear, .. } => Some(*clear),
+            _ => None,
+        })
+    }
+
+    fn locked(events: &[GameEvent]) -> bool {
+        events
+            .iter()
+            .any(|event| matches!(event, GameEvent::PieceLocked { .. }))
+    }
+
+    /// Ticks a grounded piece waits before locking when left alone
+    fn lock_delay_ticks() -> u32 {
+        (DEFAULT_LOCK_DELAY / TICK_DURATION).round() as u32
+    }
+
+    /// Tick until the active piece locks, returning how many ticks it took
+    fn ticks_until_lock(engine: &mut Engine, input: impl Fn(u32) -> TickInput) -> u32 {
+        for tick in 1..=200 {
+            if locked(&engine.tick(&input(tick))) {
+                return tick;
+            }
+        }
+        panic!("piece never locked");
+    }
+
+    #[test]
+    fn clearing_a_line_drops_the_rows_above() {
+        let mut engine = started_engine();
+        fill_row(&mut engine, BOTTOM_ROW, &[3, 4, 5, 6]);
+        engine
+            .board
+            .lock_cells(&[(0, BOTTOM_ROW - 1)], SHAPE_SQUARE);
+        place_piece(&mut engine, SHAPE_STRAIGHT, 0, 4, 5);
+
+        let clear = cleared(&engine.tick(&hard_drop())).expect("the line should clear");
+
+        assert_eq!(clear.rows, 1);
+        assert_eq!(clear.t_spin, TSpin::None);
+        assert!(!clear.perfect_clear);
+        assert_eq!(engine.board.cells().count(), 1);
+        assert!(engine.board.is_cell_occupied(0, BOTTOM_ROW));
+        assert_eq!(engine.score_manager.lines_cleared(), 1);
+    }
+
+    #[test]
+    fn clearing_every_cell_is_a_perfect_clear() {
+        let mut engine = started_engine();
+        fill_row(&mut engine, BOTTOM_ROW, &[3, 4, 5, 6]);
+        place_piece(&mut engine, SHAPE_STRAIGHT, 0, 4, 5);
+
+        let clear = cleared(&engine.tick(&hard_drop())).expect("the line should clear");
+
+        assert_eq!(clear.rows, 1);
+        assert!(clear.perfect_clear);
+        assert!(engine.board.is_empty());
+    }
+
+    #[test]
+    fn grounded_piece_locks_after_the_lock_delay() {
+        let mut engine = started_engine();
+        place_piece(&mut engine, SHAPE_TEE, 0, 4, BOTTOM_ROW);
+
+        let ticks = ticks_until_lock(&mut engine, |_| TickInput::default());
+
+        assert!(
+            ticks.abs_diff(lock_delay_ticks()) <= 1,
+            "locked after {ticks} ticks"
+        );
+    }
+
+    #[test]
+    fn moving_stops_resetting_the_lock_delay_after_the_cap() {
+        let mut engine = started_engine();
+        place_piece(&mut engine, SHAPE_TEE, 0, 4, BOTTOM_ROW);
+
+        // Wiggle left and right every tick; only the first resets restart the timer
+        let ticks = ticks_until_lock(&mut engine, |tick| TickInput {
+            left: tick % 2 == 1,
+            right: tick % 2 == 0,
+            ..TickInput::default()
+        });
+
+        assert!(ticks > lock_delay_ticks(), "resets should delay the lock");
+        assert!(
+            ticks <= DEFAULT_MAX_LOCK_RESETS + lock_delay_ticks() + 1,
+            "locked after {ticks} ticks"
+        );
+    }
+
+    #[test]
+    fn reaching_a_new_lowest_row_refunds_lock_resets() {
+        let mut engine = started_engine();
+        // A two-row ledge under the left half of the board
+        fill_row(&mut engine, BOTTOM_ROW, &[5, 6, 7, 8, 9]);
+        fill_row(&mut engine, BOTTOM_ROW - 1, &[5, 6, 7, 8, 9]);
+        place_piece(&mut engine, SHAPE_TEE, 0, 2, BOTTOM_ROW - 2);
+
+        for tick in 0..DEFAULT_MAX_LOCK_RESETS + 1 {
+            engine.tick(&TickInput {
+                left: tick % 2 == 0,
+                right: tick % 2 == 1,
+                ..TickInput::default()
+            });
+        }
+        assert_eq!(piece(&engine).lock_resets, DEFAULT_MAX_LOCK_RESETS);
+
+        // Tap right until the piece slides off the ledge, then soft drop to the floor
+        let mut tick = 0;
+        while !engine.board.can_move_down(&piece(&engine).cells()) {
+            tick += 1;
+            assert!(tick < 20, "piece never left the ledge");
+            engine.tick(&TickInput {
+                right: tick % 2 == 1,
+                ..TickInput::default()
+            });
+        }
+        while piece(&engine).cell_y < BOTTOM_ROW {
+            tick += 1;
+            assert!(tick < 120, "piece never reached the floor");
+            engine.tick(&TickInput {
+                soft_drop: true,
+                ..TickInput::default()
+            });
+        }
+
+        assert_eq!(piece(&engine).lock_resets, 0);
+    }
+
+    #[test]
+    fn hold_is_allowed_once_per_piece() {
+        let mut engine = started_engine();
+        let first_shape = piece(&engine).shape_index;
+        let hold = TickInput {
+            hold: true,
+            ..TickInput::default()
+        };
+
+        assert!(engine.tick(&hold).contains(&GameEvent::Held));
+        assert_eq!(engine.held_piece(), Some(first_shape));
+        assert!(!engine.can_hold());
+
+        // A second hold on the same piece does nothing
+        let second_shape = piece(&engine).shape_index;
+        assert!(!engine.tick(&hold).contains(&GameEvent::Held));
+        assert_eq!(engine.held_piece(), Some(first_shape));
+        assert_eq!(piece(&engine).shape_index, second_shape);
+
+        // Locking frees the hold slot for the next piece, which swaps the held one back in
+        assert!(locked(&engine.tick(&hard_drop())));
+        assert!(engine.tick(&hold).contains(&GameEvent::Held));
+        assert_eq!(piece(&engine).shape_index, first_shape);
+    }
+
+    #[test]
+    fn srs_kicks_the_straight_piece_off_the_wall() {
+        let mut engine = started_engine();
+        // Vertical against the left wall; lying flat in place would poke through it
+        place_piece(&mut engine, SHAPE_STRAIGHT, 3, 0, 10);
+
+        let events = engine.tick(&TickInput {
+            rotate_cw: true,
+            ..TickInput::default()
+        });
+
+        assert!(events.contains(&GameEvent::Rotated { quarter_turns: 1 }));
+        let piece = piece(&engine);
+        assert_eq!(piece.orientation, 0);
+        assert_eq!((piece.cell_x, piece.cell_y), (1, 10));
+        assert_eq!(piece.cells(), [(0, 10), (1, 10), (2, 10), (3, 10)]);
+    }
+
+    #[test]
+    fn srs_kicks_a_180_turn_up_off_the_floor() {
+        let mut engine = started_engine();
+        place_piece(&mut engine, SHAPE_TEE, 0, 4, BOTTOM_ROW);
+
+        let events = engine.tick(&TickInput {
+            rotate_180: true,
+            ..TickInput::default()
+        });
+
+        assert!(events.contains(&GameEvent::Rotated { quarter_turns: 2 }));
+        let piece = piece(&engine);
+        assert_eq!(piece.orientation, 2);
+        assert_eq!((piece.cell_x, piece.cell_y), (4, BOTTOM_ROW - 1));
+    }
+
+    #[test]
+    fn t_spin_with_both_front_corners_filled_is_full() {
+        let mut engine = started_engine();
+        // T-spin double slot: the T turns to point down into the hole in the bottom row
+        fill_row(&mut engine, BOTTOM_ROW, &[4]);
+        fill_row(&mut engine, BOTTOM_ROW - 1, &[3, 4, 5]);
+        engine
+            .board
+            .lock_cells(&[(3, BOTTOM_ROW - 2)], SHAPE_SQUARE);
+        place_piece(&mut engine, SHAPE_TEE, 1, 4, BOTTOM_ROW - 1);
+
+        let events = engine.tick(&TickInput {
+            rotate_cw: true,
+            hard_drop: true,
+            ..TickInput::default()
+        });
+
+        let clear = cleared(&events).expect("the T-spin should clear");
+        assert_eq!(clear.t_spin, TSpin::Full);
+        assert_eq!(clear.rows, 2);
+    }
+
+    #[test]
+    fn t_spin_with_one_front_corner_filled_is_mini() {
+        let mut engine = started_engine();
+        // Both back corners and only one of the corners the T points at
+        engine.board.lock_cells(
+            &[(3, BOTTOM_ROW - 2), (5, BOTTOM_ROW - 2), (3, BOTTOM_ROW)],
+            SHAPE_SQUARE,
+        );
+        place_piece(&mut engine, SHAPE_TEE, 1, 4, BOTTOM_ROW - 1);
+
+        let events = engine.tick(&TickInput {
+            rotate_cw: true,
+            hard_drop: true,
+            ..TickInput::default()
+        });
+
+        let clear = cleared(&events).expect("a T-spin without lines still scores");
+        assert_eq!(clear.t_spin, TSpin::Mini);
+        assert_eq!(clear.rows, 0);
+    }
+}
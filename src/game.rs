@@ -1,234 +1,481 @@
-use crate::game_data::ScoreManager;
-use crate::game_ui::GameUI;
-use crate::grid::Grid;
+use crate::controls::{Action, ControlBindings};
+use crate::engine::{
+    BOARD_HEIGHT, BOARD_WIDTH, Engine, GameEnd, GameEvent, TICK_DURATION, TickInput, t_spin_name,
+};
+use crate::game_data::{ClearInfo, ScoreManager, TSpin};
+use crate::game_over_screen::RunTime;
+use crate::game_ui::{GameUI, LineGoal};
+use crate::grid::{Grid, cell_color};
+use crate::handling::HandlingProfile;
+use crate::replay::{Replay, ReplayPlayer, ReplayRecorder};
+use crate::retris_colors::{
+    COLOR_GHOST, RGB_CALLOUT_BACK_TO_BACK, RGB_CALLOUT_LEVEL_UP, RGB_CALLOUT_PERFECT_CLEAR,
+    RGB_CALLOUT_T_SPIN,
+};
+use crate::ruleset::{Goal, Ruleset};
 use crate::sound_manager::SoundManager;
+use crate::storage::Storage;
 use crate::tetris_mobile_controller::TetrisMobileController;
-use crate::tetris_shape::TetrisShapeNode;
 use egor::input::Input;
+use egor::math::vec2;
 use egor::render::Graphics;
 
-/// Grid width in cells
-const GRID_WIDTH_CELLS: usize = 10;
-
-/// Grid height in cells (visible playfield)
-const GRID_HEIGHT_CELLS: usize = 20;
+// ============================================================================
+// HOW TO PLAY RETRIS (default bindings - all of them can be changed in Settings > Controls)
+// ============================================================================
+// Arrow Left/Right: Move piece horizontally
+// Arrow Down: Speed up falling piece
+// Space: Hard drop (instantly drop and lock the piece)
+// Arrow Up / X: Rotate piece clockwise
+// Z / Ctrl: Rotate piece counter-clockwise
+// A: Rotate piece 180 degrees
+// C / Shift: Hold piece (once per piece)
+// Enter: Start game (from title screen)
+// ============================================================================
 
 /// Minimum padding (in pixels) around the grid on all sides
 const MIN_PADDING: f32 = 40.0;
 
-/// Initial falling velocity for new pieces (cells per second)
-const SPAWN_VELOCITY: u16 = 2;
-
-/// Row index where new pieces spawn (top row of the grid, in spawn area)
-const SPAWN_ROW: i32 = 0;
+/// Length of the countdown before play picks back up after an automatic pause (in seconds)
+const RESUME_COUNTDOWN: f32 = 3.0;
 
-/// Duration of the level transition cascade effect (in seconds)
-const LEVEL_TRANSITION_DURATION: f32 = 1.5;
+/// Most engine ticks run in one frame, so a long stall doesn't fast-forward the game
+const MAX_TICKS_PER_UPDATE: u32 = 5;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum GameState {
     Playing,
-    LevelTransition { timer: f32 },
+    /// Everything is frozen and the board is hidden
+    Paused,
+    /// Board is shown again but frozen until the countdown runs out
+    Resuming { countdown: f32 },
+}
+
+/// Where a game's input comes from
+enum InputSource {
+    /// The player, with every tick written down for a replay
+    Live(ReplayRecorder),
+    /// A recorded game being played back
+    Replay(ReplayPlayer),
 }
 
+/// Replay shortcuts, in `shortcuts_down` order: pause, speed up, slow down
+const REPLAY_SHORTCUTS: [Action; 3] = [Action::HardDrop, Action::MoveRight, Action::MoveLeft];
+
+/// A game on screen: feeds egor input into the `Engine` and turns its events into
+/// sounds (kira), callouts and animations
 pub struct Game {
-    active_piece: Option<TetrisShapeNode>,
+    engine: Engine,
+    input_source: InputSource,
     grid: Grid,
-    score_manager: ScoreManager,
     ui: GameUI,
     mobile_controller: TetrisMobileController,
+    bindings: ControlBindings,
     screen_width: f32,
     screen_height: f32,
     state: GameState,
+    show_ghost: bool,               // Draw the landing preview of the active piece
+    best_score_key: Option<String>, // Where the high score goes when it isn't the regular one
+    best_time_key: Option<String>,  // Where the finish time goes for a timed goal
+    best_time: Option<u64>,         // Personal best for a timed goal, from before this game
+    new_best_time: bool,            // This game finished faster than best_time
+    pending_input: TickInput,       // Presses seen since the last tick
+    shortcuts_down: [bool; 3],      // REPLAY_SHORTCUTS that were down last frame
+    tick_accumulator: f32,          // Frame time not yet simulated
     pub is_gameover: bool,
 }
 
 impl Game {
-    pub fn new(screen_width: f32, screen_height: f32) -> Self {
+    /// Create a new game
+    /// Passing a seed reproduces that exact run; `None` picks a fresh random seed
+    pub fn new(screen_width: f32, screen_height: f32, ruleset: Ruleset, seed: Option<u64>) -> Self {
+        let handling = Storage::load_handling();
+        let engine = Engine::new(&ruleset, handling, seed);
+        let recorder = ReplayRecorder::new(engine.seed(), ruleset.clone(), handling);
+        Self::with_engine(
+            screen_width,
+            screen_height,
+            &ruleset,
+            engine,
+            InputSource::Live(recorder),
+        )
+    }
+
+    /// Play back a recorded game, exactly as it happened
+    pub fn from_replay(screen_width: f32, screen_height: f32, replay: Replay) -> Self {
+        println!(
+            "Playing replay: seed {}, {} ticks, score {}",
+            replay.seed, replay.length_ticks, replay.final_score
+        );
+        let engine = Engine::new(&replay.ruleset, replay.handling, Some(replay.seed));
+        let ruleset = replay.ruleset.clone();
+        Self::with_engine(
+            screen_width,
+            screen_height,
+            &ruleset,
+            engine,
+            InputSource::Replay(ReplayPlayer::new(replay)),
+        )
+    }
+
+    fn with_engine(
+        screen_width: f32,
+        screen_height: f32,
+        ruleset: &Ruleset,
+        mut engine: Engine,
+        input_source: InputSource,
+    ) -> Self {
+        println!("Starting game with seed {}", engine.seed());
+        let best_score_key = ruleset.best_score_key();
+        let high_score = match &best_score_key {
+            Some(key) => Storage::load_personal_bests().best_score(key),
+            None => Storage::load_game_data().high_score_for(ruleset.scoring_rule),
+        };
+        println!(
+            "Loaded {:?} high score from storage: {}",
+            ruleset.scoring_rule, high_score
+        );
+        engine.score_manager_mut().set_high_score(high_score);
+
+        let best_time_key = ruleset.best_time_key();
+        let best_time = best_time_key
+            .as_ref()
+            .and_then(|key| Storage::load_personal_bests().best_time(key));
+
         Self {
-            active_piece: None,
+            engine,
+            input_source,
             grid: Grid::new(
                 screen_width,
                 screen_height,
-                GRID_WIDTH_CELLS,
-                GRID_HEIGHT_CELLS,
+                BOARD_WIDTH,
+                BOARD_HEIGHT,
                 MIN_PADDING,
+                GameUI::side_panel_width(screen_height),
             ),
-            score_manager: ScoreManager::new(),
             ui: GameUI::new(),
             mobile_controller: TetrisMobileController::new(screen_width, screen_height),
+            bindings: Storage::load_control_bindings(),
             screen_width,
             screen_height,
             state: GameState::Playing,
+            show_ghost: Storage::load_gameplay_settings().show_ghost,
+            best_score_key,
+            best_time_key,
+            best_time,
+            new_best_time: false,
+            pending_input: TickInput::default(),
+            shortcuts_down: [false; 3],
+            tick_accumulator: 0.0,
             is_gameover: false,
         }
     }
 
-    pub fn update(&mut self, input: &Input, fixed_delta: f32, sound_manager: &mut SoundManager) {
-        match self.state {
-            GameState::LevelTransition { timer } => {
-                // Update cascade animation
-                let new_timer = timer + fixed_delta;
-
-                if new_timer >= LEVEL_TRANSITION_DURATION {
-                    // Transition complete - resume playing
-                    self.state = GameState::Playing;
-                    self.grid.clear_cascade_animation();
-                    println!("Level {} - GO!", self.score_manager.level());
-                } else {
-                    // Continue cascade animation
-                    self.state = GameState::LevelTransition { timer: new_timer };
-                    self.grid
-                        .update_cascade_animation(new_timer / LEVEL_TRANSITION_DURATION);
+    pub fn update(&mut self, input: &Input, delta: f32, sound_manager: &mut SoundManager) {
+        // Nothing moves while paused, not even callout timers
+        if self.is_paused() {
+            return;
+        }
+
+        // Mobile controls follow the active piece for touch dragging and tap-to-rotate
+        // Read first so the replay shortcuts below see this frame's touches
+        let piece_world_pos = self
+            .engine
+            .active_piece()
+            .map(|piece| self.grid.cell_world_position(piece.cell_x, piece.cell_y));
+        self.mobile_controller.update(
+            input,
+            self.screen_width,
+            self.screen_height,
+            piece_world_pos.map(|pos| pos.x),
+            piece_world_pos,
+            Some(self.grid.cell_size()),
+            Some(self.grid.visible_bottom_y()),
+        );
+
+        // Replays run at their own speed (and can be paused without the pause menu)
+        let speed = match &mut self.input_source {
+            InputSource::Live(_) => 1.0,
+            InputSource::Replay(player) => {
+                // Shortcuts act once as they go down, touch buttons report being held
+                let held = REPLAY_SHORTCUTS.map(|action| {
+                    self.bindings
+                        .held(action, input, Some(&self.mobile_controller))
+                });
+                let went_down = |index: usize| held[index] && !self.shortcuts_down[index];
+                if went_down(0) {
+                    player.toggle_pause();
+                } else if went_down(1) {
+                    player.speed_up();
+                } else if went_down(2) {
+                    player.slow_down();
                 }
-            }
-            GameState::Playing => {
-                // Check for game over condition (blocks in spawn area)
-                if self.grid.has_blocks_in_spawn_area() {
-                    self.is_gameover = true;
+                self.shortcuts_down = held;
+                if player.is_paused() {
                     return;
                 }
+                player.speed()
+            }
+        };
+        let delta = delta * speed;
 
-                self.grid.update(input, fixed_delta);
+        self.ui.update(delta);
+        self.grid.update(delta);
 
-                // Check if we need to spawn a new piece first (before updating)
-                let needs_spawn = self.active_piece.is_none();
-                if needs_spawn {
-                    self.spawn_new_piece();
-                }
+        if let GameState::Resuming { countdown } = self.state {
+            let countdown = countdown - delta;
+            if countdown > 0.0 {
+                self.state = GameState::Resuming { countdown };
+                return;
+            }
+            self.state = GameState::Playing;
+            println!("Game resumed");
+        }
 
-                // Update the active piece if it exists and isn't stopped
-                if let Some(ref mut piece) = self.active_piece {
-                    if !piece.stopped {
-                        // Calculate grid bottom Y position for red button placement
-                        let grid_visible_bottom = self.grid.visible_position().y + (self.grid.visible_height_cells() as f32 * self.grid.cell_size());
-                        piece.update(input, fixed_delta, &mut self.grid, sound_manager, &mut self.mobile_controller, self.screen_width, self.screen_height, Some(grid_visible_bottom));
-                    }
+        // Presses between ticks are kept until the next tick sees them
+        let frame_input = match self.input_source {
+            InputSource::Live(_) => self.read_input(input),
+            InputSource::Replay(_) => TickInput::default(),
+        };
+        self.pending_input = self.pending_input.merged(frame_input);
+
+        // Run the engine at its fixed rate, whatever the frame rate is
+        // Sped up replays get a proportionally bigger tick budget
+        let max_ticks = MAX_TICKS_PER_UPDATE * speed.ceil().max(1.0) as u32;
+        self.tick_accumulator += delta;
+        let mut ticks = 0;
+        while self.tick_accumulator >= TICK_DURATION && ticks < max_ticks {
+            self.tick_accumulator -= TICK_DURATION;
+            ticks += 1;
+
+            let tick_input = if ticks == 1 {
+                frame_input.merged(self.pending_input)
+            } else {
+                frame_input.held_only()
+            };
+            let events = match &mut self.input_source {
+                InputSource::Live(recorder) => recorder.tick(&mut self.engine, &tick_input),
+                InputSource::Replay(player) if player.is_finished(&self.engine) => break,
+                InputSource::Replay(player) => player.tick(&mut self.engine),
+            };
+            for event in events {
+                self.handle_event(event, sound_manager);
+            }
+        }
+        if ticks > 0 {
+            self.pending_input = TickInput::default();
+        }
+        if ticks == max_ticks {
+            // Too far behind - drop the backlog rather than catching up
+            self.tick_accumulator = 0.0;
+        }
+
+        if let Some(progress) = self.engine.level_transition_progress() {
+            self.grid.update_cascade_animation(progress);
+        }
+
+        match &self.input_source {
+            InputSource::Live(recorder) => {
+                // Every finished game is kept as the latest replay
+                if self.engine.is_game_over() && !self.is_gameover {
+                    Storage::save_replay(&recorder.finish(&self.engine));
+                    self.record_best_time();
                 }
 
-                // Check if the piece stopped and transfer it to the grid
-                if let Some(piece) = self.active_piece.take() {
-                    if piece.stopped {
-                        // Play bounce sound when piece lands
-                        sound_manager.play_bounce();
-
-                        let cells_with_colors = piece.get_occupied_cells_with_color();
-                        self.grid.mark_cells_occupied(&cells_with_colors);
-
-                        // Clear completed lines and update score
-                        let lines_cleared = self.grid.clear_completed_lines();
-
-                        if lines_cleared > 0 {
-                            // Play success sound when lines cleared
-                            sound_manager.play_success();
-
-                            // Award points for clearing lines (need mutable reference)
-                            let old_level = self.score_manager.level();
-                            let points = self
-                                .score_manager_mut()
-                                .on_rows_cleared(lines_cleared as u16);
-                            let new_level = self.score_manager.level();
-                            let combo = self.score_manager.combo_count();
-                            let multiplier = self.score_manager.multiplier();
-                            let total_score = self.score_manager.score();
-
-                            // Show different messages for special clears
-                            let clear_name = match lines_cleared {
-                                1 => "Single",
-                                2 => "Double",
-                                3 => "Triple",
-                                4 => "🎆 TETRIS",
-                                _ => "Multi",
-                            };
-
-                            // Add level indicator for high levels
-                            let level_indicator = if new_level >= 20 {
-                                " 🚀"
-                            } else if new_level >= 15 {
-                                " ⚡"
-                            } else if new_level >= 10 {
-                                " 💪"
-                            } else {
-                                ""
-                            };
-
-                            if combo > 2 {
-                                println!(
-                                    "🔥💥 {} COMBO! {}{} +{} points! ({}x next) [Lv{} | Total: {}]",
-                                    combo,
-                                    clear_name,
-                                    level_indicator,
-                                    points,
-                                    multiplier,
-                                    new_level,
-                                    total_score
-                                );
-                            } else if combo > 1 {
-                                println!(
-                                    "🔥 COMBO x{}! {}{} +{} points! ({}x next) [Lv{} | Total: {}]",
-                                    combo,
-                                    clear_name,
-                                    level_indicator,
-                                    points,
-                                    multiplier,
-                                    new_level,
-                                    total_score
-                                );
-                            } else {
-                                println!(
-                                    "{}{} cleared! +{} points ({}x next) [Lv{} | Total: {}]",
-                                    clear_name,
-                                    level_indicator,
-                                    points,
-                                    multiplier,
-                                    new_level,
-                                    total_score
-                                );
-                            }
-
-                            // Check for level up
-                            if new_level > old_level {
-                                sound_manager.play_level_up();
-                                self.start_level_transition();
-                            }
-                        } else {
-                            // Piece landed without clearing lines - reset multiplier
-                            self.score_manager.on_piece_landed_no_clear();
-                            println!("💔 Combo broken! Multiplier reset.");
-                        }
-                    } else {
-                        self.active_piece = Some(piece);
-                    }
+                // New high scores are written as soon as they happen
+                let unsaved_high_score = self.engine.score_manager_mut().take_unsaved_high_score();
+                if let Some(high_score) = unsaved_high_score {
+                    self.save_high_score(high_score);
                 }
+
+                self.is_gameover = self.engine.is_game_over();
+            }
+            InputSource::Replay(player) => {
+                // Watching a replay never touches the saved high scores or replays
+                self.is_gameover = player.is_finished(&self.engine);
             }
         }
     }
 
-    fn start_level_transition(&mut self) {
-        self.state = GameState::LevelTransition { timer: 0.0 };
-        self.active_piece = None; // Clear active piece during transition
-        self.grid.start_cascade_animation();
+    /// Write a new high score, under the mode's own key when it has one
+    pub fn save_high_score(&mut self, score: u64) {
+        match &self.best_score_key {
+            Some(key) => {
+                let mut bests = Storage::load_personal_bests();
+                bests.set_best_score(key, score);
+                Storage::save_personal_bests(&bests);
+            }
+            None => {
+                let mut game_data = Storage::load_game_data();
+                game_data.set_high_score_for(self.engine.score_manager().scoring_rule(), score);
+                Storage::save_game_data(&game_data);
+            }
+        }
+        self.engine.score_manager_mut().set_high_score(score);
+        println!("💾 Saved new high score: {}", score);
     }
 
-    fn spawn_new_piece(&mut self) {
-        let grid_pos = self.grid.position();
-        let cell_size = self.grid.cell_size();
-        let grid_width = self.grid.width_cells();
-
-        let spawn_cell_x = (grid_width / 2) as i32;
-        let spawn_cell_y = SPAWN_ROW;
-
-        let new_piece = TetrisShapeNode::new(
-            SPAWN_VELOCITY + self.score_manager.level(),
-            spawn_cell_x,
-            spawn_cell_y,
-            cell_size,
-            grid_pos,
-            grid_width,
-            self.grid.height_cells(),
-        );
+    /// Save the finish time if the goal was reached faster than ever before
+    fn record_best_time(&mut self) {
+        let Some(key) = &self.best_time_key else {
+            return;
+        };
+        if self.engine.game_end() != Some(GameEnd::GoalReached) {
+            return;
+        }
 
-        self.active_piece = Some(new_piece);
+        let millis = self.engine.elapsed_millis();
+        let mut bests = Storage::load_personal_bests();
+        if bests.record_time(key, millis) {
+            Storage::save_personal_bests(&bests);
+            self.new_best_time = true;
+            println!("💾 New personal best for {}: {} ms", key, millis);
+        }
+    }
+
+    /// Turn keyboard, mouse and mobile controls into engine input for this frame
+    fn read_input(&self, input: &Input) -> TickInput {
+        let held = |action| self.bindings.held(action, input, Some(&self.mobile_controller));
+        let pressed = |action| self.action_pressed(action, input);
+        TickInput {
+            left: held(Action::MoveLeft),
+            right: held(Action::MoveRight),
+            soft_drop: held(Action::SoftDrop),
+            hard_drop: pressed(Action::HardDrop),
+            rotate_cw: pressed(Action::RotateClockwise),
+            rotate_ccw: pressed(Action::RotateCounterClockwise),
+            rotate_180: pressed(Action::Rotate180),
+            hold: pressed(Action::Hold),
+        }
+    }
+
+    /// Print a line describing a clear (or a T-spin without lines) to the console
+    fn log_clear(&self, clear: ClearInfo, points: u64) {
+        if clear.rows == 0 {
+            println!("🌀 {}! +{} points", t_spin_name(clear), points);
+            return;
+        }
+
+        let score_manager = self.engine.score_manager();
+        let new_level = score_manager.level();
+        let combo = score_manager.combo_count();
+        let multiplier = score_manager.multiplier();
+        let total_score = score_manager.score();
+
+        // Show different messages for special clears
+        let clear_name = match (clear.t_spin, clear.rows) {
+            (TSpin::Mini | TSpin::Full, _) => format!("🌀 {}", t_spin_name(clear)),
+            (_, 1) => "Single".to_string(),
+            (_, 2) => "Double".to_string(),
+            (_, 3) => "Triple".to_string(),
+            (_, 4) => "🎆 TETRIS".to_string(),
+            _ => "Multi".to_string(),
+        };
+        let clear_name = if clear.perfect_clear {
+            format!("🏆 PERFECT CLEAR {}", clear_name)
+        } else {
+            clear_name
+        };
+
+        // Add level indicator for high levels
+        let level_indicator = if new_level >= 20 {
+            " 🚀"
+        } else if new_level >= 15 {
+            " ⚡"
+        } else if new_level >= 10 {
+            " 💪"
+        } else {
+            ""
+        };
+
+        if combo > 2 {
+            println!(
+                "🔥💥 {} COMBO! {}{} +{} points! ({}x next) [Lv{} | Total: {}]",
+                combo, clear_name, level_indicator, points, multiplier, new_level, total_score
+            );
+        } else if combo > 1 {
+            println!(
+                "🔥 COMBO x{}! {}{} +{} points! ({}x next) [Lv{} | Total: {}]",
+                combo, clear_name, level_indicator, points, multiplier, new_level, total_score
+            );
+        } else {
+            println!(
+                "{}{} cleared! +{} points ({}x next) [Lv{} | Total: {}]",
+                clear_name, level_indicator, points, multiplier, new_level, total_score
+            );
+        }
+    }
+
+    /// Play the sounds and effects for something that happened in the engine
+    fn handle_event(&mut self, event: GameEvent, sound_manager: &mut SoundManager) {
+        match event {
+            GameEvent::PieceSpawned { .. } => {}
+            // Each rotation direction has its own sound
+            GameEvent::Rotated { quarter_turns: 1 } => sound_manager.play_shuffle(),
+            GameEvent::Rotated { quarter_turns: 2 } => sound_manager.play_shuffle_180(),
+            GameEvent::Rotated { .. } => sound_manager.play_shuffle_counter_clockwise(),
+            GameEvent::Held => sound_manager.play_shuffle(),
+            GameEvent::PieceLocked { hard_drop_rows } => {
+                if hard_drop_rows > 0 {
+                    // Hard drop gets its own slam sound
+                    sound_manager.play_hard_drop();
+                } else {
+                    // Play bounce sound when piece lands
+                    sound_manager.play_bounce();
+                }
+            }
+            GameEvent::Cleared {
+                clear,
+                points,
+                back_to_back,
+            } => {
+                self.log_clear(clear, points);
+                if clear.t_spin != TSpin::None {
+                    // T-spins get their own sound and callout, with or without lines
+                    sound_manager.play_t_spin();
+                    self.ui.show_callout(&t_spin_name(clear), RGB_CALLOUT_T_SPIN);
+                } else if clear.rows > 0 {
+                    sound_manager.play_success();
+                }
+
+                if clear.perfect_clear {
+                    self.ui.show_callout("PERFECT CLEAR!", RGB_CALLOUT_PERFECT_CLEAR);
+                }
+
+                if back_to_back > 0 {
+                    let text = format!("BACK-TO-BACK x{}", back_to_back);
+                    self.ui.show_callout(&text, RGB_CALLOUT_BACK_TO_BACK);
+                }
+            }
+            GameEvent::LevelUp { level, wiped_cells } => {
+                sound_manager.play_level_up();
+                match wiped_cells {
+                    Some(cells) => self.grid.start_cascade_animation(&cells),
+                    None => {
+                        // Board stays as it is, play carries straight on
+                        self.grid.start_level_flash();
+                        let level_text = format!("LEVEL {}", level);
+                        self.ui.show_callout(&level_text, RGB_CALLOUT_LEVEL_UP);
+                    }
+                }
+            }
+            GameEvent::LevelTransitionFinished => {
+                println!("Level {} - GO!", self.engine.score_manager().level());
+                self.grid.clear_cascade_animation();
+            }
+            // is_gameover is updated once all of the frame's ticks have run
+            GameEvent::GameOver => {}
+            GameEvent::GoalReached => {
+                println!("🏁 Goal reached in {} ms", self.engine.elapsed_millis());
+                sound_manager.play_success();
+            }
+            GameEvent::TimeUp => {
+                println!("⏱️ Time up with {} points", self.engine.score_manager().score());
+                sound_manager.play_success();
+            }
+            GameEvent::GarbageAdded => sound_manager.play_bounce(),
+            GameEvent::ComboBroken => println!("💔 Combo broken! Multiplier reset."),
+        }
     }
 
     pub fn draw(&mut self, gfx: &mut Graphics, alpha: f32) {
@@ -239,30 +486,168 @@ impl Game {
             self.screen_height = screen.y;
         }
 
-        // Draw UI first so it appears behind the grid and pieces
+        // The board stays hidden while paused so pausing can't be used to plan ahead
+        if self.is_paused() {
+            return;
+        }
 
         // Draw grid and pieces on top
-        self.grid.draw(gfx, alpha);
+        self.grid.draw(gfx, self.engine.board(), alpha);
+
+        self.mobile_controller.draw(gfx);
+
+        if let Some(piece) = self.engine.active_piece() {
+            // Ghost goes underneath the piece so overlap near the floor looks right
+            if self.show_ghost {
+                let drop_rows = piece.ghost_cell_y(self.engine.board()) - piece.cell_y;
+                let ghost_cells = piece.cells().map(|(x, y)| (x, y + drop_rows));
+                self.grid.draw_piece_cells(gfx, &ghost_cells, COLOR_GHOST);
+            }
+            self.grid
+                .draw_piece_cells(gfx, &piece.cells(), cell_color(piece.shape_index));
+        }
+
+        let lines_cleared = self.engine.score_manager().lines_cleared();
+        let line_goal = match self.engine.goal() {
+            Goal::Lines(target) => Some(LineGoal::new("LINES", lines_cleared, target)),
+            Goal::GarbageLines(target) => {
+                Some(LineGoal::new("GARBAGE", self.engine.garbage_cleared(), target))
+            }
+            Goal::Endless | Goal::TimeLimit(_) => None,
+        };
+        let pause_keys = self.bindings.describe(Action::Pause);
+        self.ui
+            .draw(gfx, self.engine.score_manager(), line_goal, &pause_keys);
+        if line_goal.is_some() {
+            self.ui
+                .draw_timer(gfx, self.engine.elapsed_millis(), self.best_time);
+        }
+        if let Some(millis) = self.engine.time_left_millis() {
+            self.ui.draw_time_left(gfx, millis);
+        }
+
+        // Next queue sits to the right of the visible grid
+        let grid_top_right = vec2(
+            self.grid.visible_position().x + self.grid.width_cells() as f32 * self.grid.cell_size(),
+            self.grid.visible_position().y,
+        );
+        self.ui.draw_next_queue(
+            gfx,
+            &self.engine.next_queue(),
+            self.engine.rotation_system(),
+            grid_top_right,
+        );
 
-        if let Some(ref mut piece) = self.active_piece {
-            piece.draw(gfx, alpha, &mut self.mobile_controller);
+        self.ui.draw_callouts(gfx);
+
+        // Hold slot sits to the left of the visible grid
+        self.ui.draw_hold_slot(
+            gfx,
+            self.engine.held_piece(),
+            self.engine.can_hold(),
+            self.engine.rotation_system(),
+            self.grid.visible_position(),
+        );
+
+        if let InputSource::Replay(player) = &self.input_source {
+            let status = if player.is_paused() {
+                "REPLAY - PAUSED".to_string()
+            } else {
+                format!("REPLAY x{}", player.speed())
+            };
+            self.ui
+                .draw_replay_status(gfx, &status, self.grid.visible_position().y);
         }
 
-        self.ui.draw(gfx, &self.score_manager);
+        if let GameState::Resuming { countdown } = self.state {
+            self.ui.draw_countdown(gfx, countdown);
+        }
     }
 
     /// Get a reference to the score manager for displaying stats
     pub fn score_manager(&self) -> &ScoreManager {
-        &self.score_manager
+        self.engine.score_manager()
+    }
+
+    /// Turn the ghost piece on or off (after settings change mid-game)
+    pub fn set_show_ghost(&mut self, show_ghost: bool) {
+        self.show_ghost = show_ghost;
+    }
+
+    /// Why the game finished (None while it's still going)
+    pub fn game_end(&self) -> Option<GameEnd> {
+        self.engine.game_end()
+    }
+
+    /// Finish time of a timed goal that was reached, with the personal best it was up against
+    pub fn run_time(&self) -> Option<RunTime> {
+        self.best_time_key.as_ref()?;
+        if self.engine.game_end() != Some(GameEnd::GoalReached) {
+            return None;
+        }
+        Some(RunTime {
+            millis: self.engine.elapsed_millis(),
+            best_millis: self.best_time,
+            new_best: self.new_best_time,
+        })
     }
 
-    /// Get a mutable reference to the score manager
-    pub fn score_manager_mut(&mut self) -> &mut ScoreManager {
-        &mut self.score_manager
+    /// Get the seed driving this game's randomness (share it to replay the run)
+    pub fn seed(&self) -> u64 {
+        self.engine.seed()
+    }
+
+    /// Pause the game, freezing gravity, lock delay and animations
+    pub fn pause(&mut self) {
+        if self.is_paused() {
+            return;
+        }
+        self.state = GameState::Paused;
+        self.pending_input = TickInput::default();
+        println!("Game paused");
     }
 
-    /// Check if mobile controller quit button was pressed
-    pub fn mobile_quit_pressed(&self) -> bool {
-        self.mobile_controller.quit_pressed()
+    /// Resume a paused game where it left off
+    pub fn resume(&mut self) {
+        if self.is_paused() {
+            self.state = GameState::Playing;
+            self.tick_accumulator = 0.0;
+            println!("Game resumed");
+        }
+    }
+
+    /// Resume a paused game after a short countdown (used after the window regains focus)
+    pub fn resume_with_countdown(&mut self) {
+        if self.is_paused() {
+            self.state = GameState::Resuming {
+                countdown: RESUME_COUNTDOWN,
+            };
+            self.tick_accumulator = 0.0;
+        }
+    }
+
+    /// Check if the game is paused
+    pub fn is_paused(&self) -> bool {
+        self.state == GameState::Paused
+    }
+
+    /// Check if any control bound to an action (including the mobile buttons) was pressed
+    pub fn action_pressed(&self, action: Action, input: &Input) -> bool {
+        self.bindings
+            .pressed(action, input, Some(&self.mobile_controller))
+    }
+
+    /// Swap in new control bindings (after they were changed mid-game)
+    pub fn set_bindings(&mut self, bindings: ControlBindings) {
+        self.bindings = bindings;
+    }
+
+    /// Apply a new handling profile (after it was changed mid-game)
+    /// Replays keep the handling they were recorded with
+    pub fn set_handling(&mut self, handling: HandlingProfile) {
+        if let InputSource::Live(recorder) = &mut self.input_source {
+            recorder.record_handling(self.engine.ticks(), handling);
+            self.engine.set_handling(handling);
+        }
     }
 }
@@ -1,3 +1,4 @@
+use crate::scoring::{ClearContext, ClearScore, ScoringRule, ScoringRuleKind};
 use std::collections::HashMap;
 
 /// A generic table data structure for game grids
@@ -98,7 +99,6 @@ impl<T> GameTable<T> {
             })
     }
 
-    #[allow(dead_code)]
     pub fn iter_row(&self, row: i32) -> impl Iterator<Item = (i32, &T)> {
         self.data
             .get(row as usize)
@@ -133,6 +133,15 @@ impl<T> GameTable<T> {
         }
     }
 
+    /// Shift every row up by one, dropping the top row and leaving an empty row at the bottom
+    pub fn shift_up(&mut self) {
+        if self.data.is_empty() {
+            return;
+        }
+        self.data.remove(0);
+        self.data.push(HashMap::new());
+    }
+
     pub fn remove_row_and_shift_down(&mut self, row: i32) -> bool {
         if !self.is_valid_position(0, row) {
             return false;
@@ -157,79 +166,71 @@ impl<T> GameTable<T> {
     }
 }
 
-/// Manages the scoring system with EXPONENTIAL multipliers and level progression
-///
-/// ## Scoring Formula:
-/// `points = 137 × rows_bonus × previous_multiplier × combo_multiplier × level_multiplier`
-///
-/// - **Base Points**: 137 (prime number for interesting scores)
-///
-/// - **Rows Bonus** (exponential - 2^n - 1):
-///   - 1 row: 1x
-///   - 2 rows: 3x
-///   - 3 rows: 7x
-///   - 4 rows (Tetris!): 15x 🔥
-///
-/// - **Previous Multiplier**: Carries over from last clear (starts at 1x)
-///   - Clear a Tetris → next clear gets 15x multiplier!
-///   - This compounds with the next clear's rows_bonus
-///
-/// - **Combo Multiplier** (exponential - 2^(combo-1)):
-///   - 1st consecutive clear: 1x
-///   - 2nd consecutive clear: 2x
-///   - 3rd consecutive clear: 4x
-///   - 4th consecutive clear: 8x
-///   - 5th consecutive clear: 16x 💥
-///   - Resets to 0 if a piece lands without clearing
+/// How a T piece was spun into place before it locked
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TSpin {
+    #[default]
+    None,
+    /// Only one front corner filled (small kick into a shallow slot)
+    Mini,
+    /// Both front corners filled, or the piece got there with the big SRS kick
+    Full,
+}
+
+/// Everything about a locked piece that matters for scoring
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ClearInfo {
+    /// Number of rows cleared by the piece
+    pub rows: u16,
+    /// Whether the piece was spun in as a T-spin
+    pub t_spin: TSpin,
+    /// Whether the clear left the board completely empty
+    pub perfect_clear: bool,
+}
+
+/// Manages score, lines, level progression, combos and back-to-back chains
+/// How many points each clear is worth is decided by the game's `ScoringRule`
 ///
-/// - **Level Multiplier**: Rewards survival at high speeds!
-///   - Level 0-4: 1x
-///   - Level 5-9: 2x
-///   - Level 10-14: 3x
-///   - Level 15-19: 5x
-///   - Level 20+: 8x 🚀
+/// - **Combos**: Consecutive clears without missing, reset when a piece lands without clearing
 ///
-/// ## Example Scoring Sequences:
-/// - Level 0, Clear 1 row: 137 × 1 × 1 × 1 × 1 = 137 points
-/// - Level 0, Clear 4 rows (Tetris): 137 × 15 × 1 × 1 × 1 = 2,055 points
-/// - Level 0, Clear 4 more rows (combo!): 137 × 15 × 15 × 2 × 1 = 61,650 points! 🤯
-/// - Level 10, Clear 4 rows (Tetris): 137 × 15 × 1 × 1 × 3 = 6,165 points!
-/// - Level 20, Clear 4 rows (Tetris): 137 × 15 × 1 × 1 × 8 = 16,440 points!! 💰
-/// - Level 20, Clear 4 more (combo!): 137 × 15 × 15 × 2 × 8 = 493,200 points!!! 🔥💥🚀
+/// - **Back-to-Back**: Difficult clears (Tetrises and T-spins with lines) in a row
+///   - Pieces that clear nothing keep the chain going, any easy clear (1-3 rows) breaks it
 ///
 /// - **Level progression**: Every 10 lines cleared increases level and drop speed
+///
+/// - **High scores**: Kept separately for each scoring rule
 pub struct ScoreManager {
     score: u64,
     lines_cleared: u16,
     level: u16,
-    current_multiplier: u32, // Based on rows cleared in one drop
+    current_multiplier: u32, // Carried into the next clear (Retris scoring)
     combo_count: u32,        // Consecutive clears without missing
+    difficult_clear_streak: u32, // Consecutive difficult clears (Tetris / T-spin) for back-to-back
     high_score: u64,
     high_score_needs_sync: bool, // True if high score needs to be uploaded to server
-    base_points_per_row: u64,
+    high_score_unsaved: bool,    // True if a new high score hasn't been written to storage yet
+    scoring_rule: Box<dyn ScoringRule>,
+    scoring_rule_kind: ScoringRuleKind,
     lines_per_level: u16,
 }
 
 impl ScoreManager {
-    /// Create a new ScoreManager with default settings
-    /// Base points per row is 137 by default (a prime number for interesting scores!)
+    /// Create a new ScoreManager using the given scoring rule
     /// Level increases every 10 lines
-    /// Loads high score from storage
-    pub fn new() -> Self {
-        use crate::storage::Storage;
-
-        let game_data = Storage::load_game_data();
-        println!("Loaded high score from storage: {}", game_data.high_score);
-
+    /// The high score starts at 0; callers load the stored one with `set_high_score`
+    pub fn new(scoring_rule: ScoringRuleKind) -> Self {
         Self {
             score: 0,
             lines_cleared: 0,
             level: 0,
             current_multiplier: 1,
             combo_count: 0,
-            high_score: game_data.high_score,
+            difficult_clear_streak: 0,
+            high_score: 0,
             high_score_needs_sync: false,
-            base_points_per_row: 137, // Prime number for more interesting scores
+            high_score_unsaved: false,
+            scoring_rule: scoring_rule.create(),
+            scoring_rule_kind: scoring_rule,
             lines_per_level: 10,
         }
     }
@@ -259,100 +260,95 @@ impl ScoreManager {
         self.combo_count
     }
 
+    /// Get the back-to-back count (difficult clears chained after the first one)
+    pub fn back_to_back_count(&self) -> u32 {
+        self.difficult_clear_streak.saturating_sub(1)
+    }
+
     /// Get the high score
     pub fn high_score(&self) -> u64 {
         self.high_score
     }
 
-    /// Call this when rows are cleared
+    /// Get the scoring rule this game is scored with
+    pub fn scoring_rule(&self) -> ScoringRuleKind {
+        self.scoring_rule_kind
+    }
+
+    /// Call this when rows are cleared (or a T-spin locks without clearing any)
     /// Returns the points awarded for this clear
-    pub fn on_rows_cleared(&mut self, rows_cleared: u16) -> u64 {
-        if rows_cleared == 0 {
-            return 0;
+    pub fn on_rows_cleared(&mut self, clear: ClearInfo) -> u64 {
+        if clear.rows == 0 {
+            // Zero-line T-spins may still score, but leave combo, multiplier and B2B alone
+            if clear.t_spin == TSpin::None {
+                return 0;
+            }
+            let context = ClearContext {
+                clear,
+                level: self.level,
+                combo_count: self.combo_count,
+                previous_multiplier: self.current_multiplier,
+                back_to_back: false,
+            };
+            let points = self.scoring_rule.score_clear(&context).points;
+            self.add_points(points);
+            return points;
         }
 
         // Capture current level BEFORE updating (for scoring)
         let level_at_clear = self.level;
 
         // Update lines and check for level up
-        let old_level = self.level;
-        self.lines_cleared += rows_cleared;
+        self.lines_cleared = self.lines_cleared.saturating_add(clear.rows);
         self.level = self.lines_cleared / self.lines_per_level;
 
         // Increment combo count
-        self.combo_count += 1;
-
-        // Calculate points with EXPONENTIAL multipliers:
-        //
-        // 1. Base points per row (137 by default - a prime number)
-        //
-        // 2. Rows bonus - exponential based on rows cleared at once:
-        //    - 1 row: 1x (base)
-        //    - 2 rows: 3x (1.5x per row)
-        //    - 3 rows: 7x (2.3x per row)
-        //    - 4 rows (Tetris!): 15x (3.75x per row!)
-        let rows_bonus_multiplier = match rows_cleared {
-            1 => 1,
-            2 => 3,
-            3 => 7,
-            4 => 15,
-            _ => (1 << rows_cleared) - 1, // 2^n - 1 for higher
+        self.combo_count = self.combo_count.saturating_add(1);
+
+        // Tetrises and T-spins are difficult; any other clear breaks the back-to-back chain
+        let difficult = clear.rows >= 4 || clear.t_spin != TSpin::None;
+        let back_to_back = difficult && self.difficult_clear_streak > 0;
+        self.difficult_clear_streak = if difficult {
+            self.difficult_clear_streak.saturating_add(1)
+        } else {
+            0
         };
 
-        // 3. Previous clear multiplier (from last drop)
-        let previous_multiplier = self.current_multiplier as u64;
-
-        // 4. Combo multiplier - gets exponential with chain length:
-        //    Combo 1: 1x
-        //    Combo 2: 2x
-        //    Combo 3: 4x
-        //    Combo 4: 8x
-        //    Combo 5: 16x (insane!)
-        let combo_multiplier = 1u64 << (self.combo_count - 1); // 2^(combo-1)
-
-        // 5. Level multiplier - reward players for surviving at high levels!
-        //    Level 0-4: 1x
-        //    Level 5-9: 2x
-        //    Level 10-14: 3x
-        //    Level 15-19: 5x
-        //    Level 20+: 8x (crazy difficulty = crazy points!)
-        let level_multiplier = match level_at_clear {
-            0..=4 => 1,
-            5..=9 => 2,
-            10..=14 => 3,
-            15..=19 => 5,
-            _ => 8, // Level 20+
+        let context = ClearContext {
+            clear,
+            level: level_at_clear,
+            combo_count: self.combo_count,
+            previous_multiplier: self.current_multiplier,
+            back_to_back,
         };
+        let ClearScore {
+            points,
+            next_multiplier,
+        } = self.scoring_rule.score_clear(&context);
 
-        // Final formula: base × rows_bonus × previous_multiplier × combo_multiplier × level_multiplier
-        let points = self.base_points_per_row
-            * rows_bonus_multiplier
-            * previous_multiplier
-            * combo_multiplier
-            * level_multiplier;
+        self.add_points(points);
+        self.current_multiplier = next_multiplier;
 
-        self.score += points;
+        points
+    }
+
+    /// Call this when a piece is hard dropped
+    /// Returns the points awarded for the cells it fell
+    pub fn on_hard_drop(&mut self, cells_dropped: u32) -> u64 {
+        let points = self.scoring_rule.hard_drop_points(cells_dropped);
+        self.add_points(points);
+        points
+    }
+
+    /// Add points to the score, flagging a new high score for saving
+    fn add_points(&mut self, points: u64) {
+        self.score = self.score.saturating_add(points);
 
-        // Check if this is a new high score and save immediately
         if self.score > self.high_score {
             self.high_score = self.score;
             self.high_score_needs_sync = true;
-            self.save_high_score();
-        }
-
-        // Set the multiplier for the NEXT clear based on this clear
-        // This creates insane compounding:
-        // - Clear 4 rows -> 15x multiplier for next clear
-        // - Clear 4 more rows -> 15x on THAT too!
-        self.current_multiplier = rows_bonus_multiplier as u32;
-
-        // If we leveled up, you could trigger a callback here
-        // For now, the game can check if level changed by comparing before/after
-        if self.level > old_level {
-            // Level up occurred!
+            self.high_score_unsaved = true;
         }
-
-        points
     }
 
     /// Call this when a piece lands without clearing any rows
@@ -362,25 +358,22 @@ impl ScoreManager {
         self.combo_count = 0;
     }
 
-    /// Save the current high score to storage
-    /// This is called automatically when a new high score is achieved
-    fn save_high_score(&self) {
-        use crate::storage::{GameData, Storage};
-        Storage::save_game_data(&GameData {
-            high_score: self.high_score,
-        });
-        println!("💾 Saved new high score: {}", self.high_score);
+    /// Hand over a new high score that still needs saving, clearing the flag
+    /// Scoring itself never touches storage so it can run headless
+    pub fn take_unsaved_high_score(&mut self) -> Option<u64> {
+        std::mem::take(&mut self.high_score_unsaved).then_some(self.high_score)
     }
 
-    /// Manually set the high score (useful when loading from server)
+    /// Manually set the high score (useful when loading from storage or the server)
     pub fn set_high_score(&mut self, high_score: u64) {
         self.high_score = high_score;
         self.high_score_needs_sync = false;
+        self.high_score_unsaved = false;
     }
 }
 
 impl Default for ScoreManager {
     fn default() -> Self {
-        Self::new()
+        Self::new(ScoringRuleKind::default())
     }
 }
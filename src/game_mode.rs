@@ -0,0 +1,102 @@
+use crate::garbage::GarbageSettings;
+use crate::gravity::GravityKind;
+use crate::rule_options::RuleOptions;
+use crate::ruleset::{Goal, LevelUpEffect, Ruleset};
+
+/// Lines to clear in a sprint
+const SPRINT_LINES: u16 = 40;
+
+/// Length of an ultra game (in seconds)
+const ULTRA_SECONDS: u32 = 120;
+
+/// Garbage lines to clear in dig (the board starts with exactly this many)
+const DIG_LINES: u16 = 10;
+
+/// Ways to play, picked on the title screen
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GameMode {
+    /// Endless survival
+    #[default]
+    Marathon,
+    /// Clear 40 lines against the clock
+    Sprint,
+    /// Score as much as possible in 2 minutes
+    Ultra,
+    /// Dig through 10 rows of garbage against the clock
+    Dig,
+}
+
+impl GameMode {
+    /// Every mode, in the order the title screen cycles through them
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Marathon,
+        GameMode::Sprint,
+        GameMode::Ultra,
+        GameMode::Dig,
+    ];
+
+    /// Name shown on the title screen
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Marathon => "Marathon",
+            GameMode::Sprint => "Sprint 40L",
+            GameMode::Ultra => "Ultra 2:00",
+            GameMode::Dig => "Dig 10L",
+        }
+    }
+
+    /// The rules a game in this mode is played with
+    /// The player's rule options fill in everything the mode doesn't decide itself
+    pub fn ruleset(self, options: &RuleOptions) -> Ruleset {
+        let base = Ruleset {
+            randomizer: options.randomizer,
+            rotation_system: options.rotation_system,
+            scoring_rule: options.scoring_rule,
+            gravity: options.gravity,
+            ..Ruleset::default()
+        };
+        match self {
+            GameMode::Marathon => Ruleset {
+                level_up_effect: options.marathon_level_up,
+                ..base
+            },
+            GameMode::Sprint => Ruleset {
+                goal: Goal::Lines(SPRINT_LINES),
+                // Wiping the board on level up would clear the stack for free
+                level_up_effect: LevelUpEffect::Flash,
+                ..base
+            },
+            GameMode::Ultra => Ruleset {
+                goal: Goal::TimeLimit(ULTRA_SECONDS),
+                gravity: GravityKind::Fixed,
+                // The wipe pause would eat into the clock
+                level_up_effect: LevelUpEffect::Flash,
+                ..base
+            },
+            GameMode::Dig => Ruleset {
+                goal: Goal::GarbageLines(DIG_LINES),
+                garbage: Some(GarbageSettings {
+                    starting_rows: DIG_LINES,
+                    messiness: f32::from(options.dig_messiness) / 100.0,
+                    rise_interval: (options.dig_rise_seconds > 0)
+                        .then(|| f32::from(options.dig_rise_seconds)),
+                }),
+                // Wiping the board on level up would clear the garbage for free
+                level_up_effect: LevelUpEffect::Flash,
+                ..base
+            },
+        }
+    }
+
+    /// The mode after this one (wrapping around)
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// The mode before this one (wrapping around)
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|&mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
@@ -1,14 +1,26 @@
 use crate::coordinate_system::CoordinateSystem;
+use crate::engine::GameEnd;
 use crate::game_data::ScoreManager;
+use crate::game_ui::format_time;
 use crate::retris_colors::*;
 use crate::retris_ui::Button;
 use egor::input::Input;
 use egor::render::Graphics;
 
+/// Finish time of a timed run, shown under the title
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunTime {
+    pub millis: u64,
+    /// Personal best before this run
+    pub best_millis: Option<u64>,
+    pub new_best: bool,
+}
+
 pub struct GameOverScreen {
     quit_button: Button,
     back_to_menu_button: Button,
     retry_button: Button,
+    replay_button: Button,
 }
 
 impl GameOverScreen {
@@ -66,6 +78,13 @@ impl GameOverScreen {
                 button_height,
                 "Retry",
             ),
+            replay_button: Button::new(
+                button_left_x,
+                start_y + (button_height + button_spacing) * 3.0,
+                button_width,
+                button_height,
+                "Watch Replay",
+            ),
         }
     }
 
@@ -106,11 +125,19 @@ impl GameOverScreen {
             button_height,
             "Retry",
         );
+        self.replay_button = Button::new(
+            button_left_x,
+            start_y + (button_height + button_spacing) * 3.0,
+            button_width,
+            button_height,
+            "Watch Replay",
+        );
 
         // Also call update in case Button has its own update logic
         self.quit_button.update(screen_width, screen_height);
         self.back_to_menu_button.update(screen_width, screen_height);
         self.retry_button.update(screen_width, screen_height);
+        self.replay_button.update(screen_width, screen_height);
     }
 
     /// Handle input for game over screen
@@ -135,6 +162,11 @@ impl GameOverScreen {
             .is_clicked(input, screen_width, screen_height)
         {
             GameOverAction::Retry
+        } else if self
+            .replay_button
+            .is_clicked(input, screen_width, screen_height)
+        {
+            GameOverAction::WatchReplay
         } else {
             GameOverAction::None
         }
@@ -144,6 +176,9 @@ impl GameOverScreen {
         &self,
         gfx: &mut Graphics,
         score_manager: &ScoreManager,
+        seed: u64,
+        end: Option<GameEnd>,
+        run_time: Option<RunTime>,
         screen_width: f32,
         screen_height: f32,
     ) {
@@ -154,8 +189,12 @@ impl GameOverScreen {
         // Text sizes already use percentage-based scaling, so they're aspect-ratio-aware
         let scale_factor = (screen_height / 1048.0).max(0.5).min(2.0);
 
-        // Draw "GAME OVER" text in the center
-        let title_text = "GAME OVER";
+        // Draw "GAME OVER" (or "FINISHED!" / "Time!" when the goal or clock ran out) in the center
+        let title_text = match end {
+            Some(GameEnd::GoalReached) => "FINISHED!",
+            Some(GameEnd::TimeUp) => "Time!",
+            _ => "GAME OVER",
+        };
         let title_size = (screen_height * 0.069).max(36.0).min(144.0);
         // Use center_text_x which properly calculates the left edge to center the text
         // This ensures equal spacing on both sides
@@ -171,6 +210,33 @@ impl GameOverScreen {
             .size(title_size)
             .color(COLOR_TEXT_GREEN);
 
+        // Draw the finish time of a timed run
+        if let Some(run_time) = run_time {
+            let time_text = match run_time.best_millis {
+                _ if run_time.new_best => {
+                    format!("Time: {} - NEW BEST!", format_time(run_time.millis))
+                }
+                Some(best) => format!(
+                    "Time: {} (best {})",
+                    format_time(run_time.millis),
+                    format_time(best)
+                ),
+                None => format!("Time: {}", format_time(run_time.millis)),
+            };
+            let time_size = (screen_height * 0.027).clamp(14.0, 56.0);
+            let time_world_x = coords.center_text_x(&time_text, time_size, 0.5);
+            let time_screen_pos =
+                coords.world_to_screen(egor::math::vec2(time_world_x, -170.0 * scale_factor));
+            gfx.text(&time_text)
+                .at(time_screen_pos)
+                .size(time_size)
+                .color(if run_time.new_best {
+                    COLOR_ORANGE
+                } else {
+                    COLOR_TEXT_GREEN
+                });
+        }
+
         // Draw score details
         let score = score_manager.score();
         let high_score = score_manager.high_score();
@@ -226,11 +292,23 @@ impl GameOverScreen {
             .size(stats_size)
             .color(COLOR_DARK_GRAY);
 
+        // Draw the seed so the run can be shared and replayed
+        let seed_text = format!("Seed: {}", seed);
+        let seed_size = (screen_height * 0.019).max(12.0).min(32.0);
+        let seed_world_x = coords.center_text_x(&seed_text, seed_size, 0.5);
+        let seed_world_y = 330.0 * scale_factor; // Below the buttons
+        let seed_screen_pos = coords.world_to_screen(egor::math::vec2(seed_world_x, seed_world_y));
+        gfx.text(&seed_text)
+            .at(seed_screen_pos)
+            .size(seed_size)
+            .color(COLOR_DARK_GRAY);
+
         // Draw buttons (positions should be updated via update() before calling)
         self.quit_button.draw(gfx, screen_width, screen_height);
         self.back_to_menu_button
             .draw(gfx, screen_width, screen_height);
         self.retry_button.draw(gfx, screen_width, screen_height);
+        self.replay_button.draw(gfx, screen_width, screen_height);
     }
 }
 
@@ -240,6 +318,8 @@ pub enum GameOverAction {
     Quit,
     BackToMenu,
     Retry,
+    /// Play back the game that just ended
+    WatchReplay,
 }
 
 impl Default for GameOverScreen {
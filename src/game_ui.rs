@@ -1,21 +1,275 @@
 use crate::coordinate_system::CoordinateSystem;
 use crate::game_data::ScoreManager;
 use crate::retris_colors::*;
-use egor::math::vec2;
-use egor::render::Graphics;
+use crate::rotation::RotationSystem;
+use crate::scoring::{RetrisScoring, ScoringRuleKind};
+use crate::tetris_shape::ShapeName;
+use egor::math::{Vec2, vec2};
+use egor::render::{Color, Graphics};
+
+/// Progress toward a number of lines that finishes the game, like "LINES: 12/40"
+#[derive(Debug, Clone, Copy)]
+pub struct LineGoal {
+    label: &'static str,
+    cleared: u16,
+    target: u16,
+}
+
+impl LineGoal {
+    pub fn new(label: &'static str, cleared: u16, target: u16) -> Self {
+        Self {
+            label,
+            cleared,
+            target,
+        }
+    }
+}
+
+/// Format a time in milliseconds as m:ss.mmm
+pub fn format_time(millis: u64) -> String {
+    format!(
+        "{}:{:02}.{:03}",
+        millis / 60_000,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
+}
+
+/// A short-lived message announcing a special clear (T-spin, etc.)
+struct Callout {
+    text: String,
+    color_rgb: [f32; 3], // RGB color (alpha is handled by the fade out)
+    timer: f32, // Seconds since the callout appeared
+}
 
 /// Renders the game UI (score, level, etc.) behind the game board
 /// Text gets obscured by blocks as the player fills the board
-pub struct GameUI {}
+pub struct GameUI {
+    callouts: Vec<Callout>,
+}
 
 impl GameUI {
+    /// How long a callout stays on screen (seconds)
+    const CALLOUT_DURATION: f32 = 1.5;
+    /// Callouts fade out over this final part of their duration (seconds)
+    const CALLOUT_FADE: f32 = 0.5;
+
+    /// Base cell size for preview pieces (normalized to 1048px height)
+    const BASE_PREVIEW_CELL_SIZE: f32 = 16.0;
+    /// Base gap between the grid and a side panel (normalized to 1048px height)
+    const BASE_PANEL_GAP: f32 = 10.0;
+    /// Widest preview piece in cells (the Straight)
+    const PREVIEW_PIECE_CELLS: f32 = 4.0;
+
     pub fn new() -> Self {
-        Self {}
+        Self {
+            callouts: Vec::new(),
+        }
+    }
+
+    /// Advance callout timers and drop expired callouts
+    pub fn update(&mut self, delta: f32) {
+        for callout in self.callouts.iter_mut() {
+            callout.timer += delta;
+        }
+        self.callouts
+            .retain(|callout| callout.timer < Self::CALLOUT_DURATION);
+    }
+
+    /// Announce a special clear; several callouts at once stack downwards
+    pub fn show_callout(&mut self, text: &str, color_rgb: [f32; 3]) {
+        self.callouts.push(Callout {
+            text: text.to_string(),
+            color_rgb,
+            timer: 0.0,
+        });
+    }
+
+    /// Draw active callouts over the top of the board
+    /// This should be called AFTER drawing the grid and pieces so nothing hides them
+    pub fn draw_callouts(&self, gfx: &mut Graphics) {
+        let screen_height = gfx.screen_size().y;
+        let scale = Self::scale_factor(screen_height);
+        let size = (screen_height * 0.042).max(24.0).min(72.0);
+
+        let mut callout_y = -320.0 * scale;
+        for callout in &self.callouts {
+            // Fade out over the last part of the callout's life
+            let remaining = Self::CALLOUT_DURATION - callout.timer;
+            let alpha = (remaining / Self::CALLOUT_FADE).min(1.0);
+            let [r, g, b] = callout.color_rgb;
+            let color = Color::new([r, g, b, alpha]);
+
+            self.draw_centered_text(gfx, &callout.text, callout_y, size, color);
+            callout_y += size * 1.2;
+        }
+    }
+
+    /// Scale factor based on screen height, clamped to prevent extreme sizes
+    fn scale_factor(screen_height: f32) -> f32 {
+        (screen_height / 1048.0).max(0.5).min(2.0)
+    }
+
+    /// Width needed beside the grid for a side panel (next queue, hold slot)
+    pub fn side_panel_width(screen_height: f32) -> f32 {
+        let scale = Self::scale_factor(screen_height);
+        (Self::BASE_PREVIEW_CELL_SIZE * Self::PREVIEW_PIECE_CELLS + Self::BASE_PANEL_GAP * 2.0)
+            * scale
+    }
+
+    /// Draw the upcoming pieces in a column to the right of the grid
+    /// Pieces are shown in the rotation system's spawn orientation
+    /// grid_top_right: world position of the visible grid's top-right corner
+    pub fn draw_next_queue(
+        &self,
+        gfx: &mut Graphics,
+        next_pieces: &[i32],
+        rotation_system: &dyn RotationSystem,
+        grid_top_right: Vec2,
+    ) {
+        let screen_height = gfx.screen_size().y;
+        let scale = Self::scale_factor(screen_height);
+        let cell_size = Self::BASE_PREVIEW_CELL_SIZE * scale;
+        let gap = Self::BASE_PANEL_GAP * scale;
+        let center_x = grid_top_right.x + gap + (cell_size * Self::PREVIEW_PIECE_CELLS) / 2.0;
+
+        // "NEXT" label above the queue
+        let label_size = (screen_height * 0.019).max(14.0).min(32.0);
+        self.draw_panel_label(gfx, "NEXT", center_x, grid_top_right.y, label_size);
+
+        // Stack pieces downwards, each taking only as much room as it needs
+        let mut top_y = grid_top_right.y + label_size + gap;
+        for &shape_index in next_pieces {
+            let shape = Self::spawn_shape(shape_index, rotation_system);
+            let color = shape.color();
+            let height =
+                self.draw_mini_piece(gfx, &shape, vec2(center_x, top_y), cell_size, color);
+            top_y += height + cell_size;
+        }
+    }
+
+    /// Draw the held piece in a slot to the left of the grid
+    /// The piece is grayed out while holding is unavailable (already used for this piece)
+    /// grid_top_left: world position of the visible grid's top-left corner
+    pub fn draw_hold_slot(
+        &self,
+        gfx: &mut Graphics,
+        held_piece: Option<i32>,
+        can_hold: bool,
+        rotation_system: &dyn RotationSystem,
+        grid_top_left: Vec2,
+    ) {
+        let screen_height = gfx.screen_size().y;
+        let scale = Self::scale_factor(screen_height);
+        let cell_size = Self::BASE_PREVIEW_CELL_SIZE * scale;
+        let gap = Self::BASE_PANEL_GAP * scale;
+        let center_x = grid_top_left.x - gap - (cell_size * Self::PREVIEW_PIECE_CELLS) / 2.0;
+
+        // "HOLD" label above the slot
+        let label_size = (screen_height * 0.019).max(14.0).min(32.0);
+        self.draw_panel_label(gfx, "HOLD", center_x, grid_top_left.y, label_size);
+
+        if let Some(shape_index) = held_piece {
+            let shape = Self::spawn_shape(shape_index, rotation_system);
+            let color = if can_hold {
+                shape.color()
+            } else {
+                COLOR_DARK_GRAY
+            };
+            let top_y = grid_top_left.y + label_size + gap;
+            self.draw_mini_piece(gfx, &shape, vec2(center_x, top_y), cell_size, color);
+        }
+    }
+
+    /// Build a shape laid out in the rotation system's spawn orientation
+    fn spawn_shape(shape_index: i32, rotation_system: &dyn RotationSystem) -> ShapeName {
+        let mut shape = ShapeName::get_shape_by_index(shape_index);
+        shape.set_cells(&rotation_system.cells(shape_index, 0));
+        shape
+    }
+
+    /// Draw a small piece horizontally centered on top_center
+    /// Returns the height of the drawn piece in pixels
+    fn draw_mini_piece(
+        &self,
+        gfx: &mut Graphics,
+        shape: &ShapeName,
+        top_center: Vec2,
+        cell_size: f32,
+        color: Color,
+    ) -> f32 {
+        const BORDER_WIDTH: f32 = 1.0;
+
+        // Find the piece's bounding box in cell units so it can be centered
+        let dimensions = shape.get_dimensions();
+        let min_x = dimensions
+            .iter()
+            .map(|d| d.position.x)
+            .fold(f32::MAX, f32::min);
+        let max_x = dimensions
+            .iter()
+            .map(|d| d.position.x)
+            .fold(f32::MIN, f32::max);
+        let min_y = dimensions
+            .iter()
+            .map(|d| d.position.y)
+            .fold(f32::MAX, f32::min);
+        let max_y = dimensions
+            .iter()
+            .map(|d| d.position.y)
+            .fold(f32::MIN, f32::max);
+        let width = (max_x - min_x + 1.0) * cell_size;
+        let height = (max_y - min_y + 1.0) * cell_size;
+        let origin = vec2(top_center.x - width / 2.0, top_center.y);
+
+        let fill_size = vec2(
+            cell_size - BORDER_WIDTH * 2.0,
+            cell_size - BORDER_WIDTH * 2.0,
+        );
+        for dimension in dimensions {
+            let block_pos = origin
+                + vec2(
+                    (dimension.position.x - min_x) * cell_size,
+                    (dimension.position.y - min_y) * cell_size,
+                );
+            gfx.rect()
+                .size(fill_size)
+                .at(block_pos + vec2(BORDER_WIDTH, BORDER_WIDTH))
+                .color(color);
+        }
+
+        height
+    }
+
+    /// Draw a side panel label centered on world_x, with its top at world_y
+    fn draw_panel_label(
+        &self,
+        gfx: &mut Graphics,
+        text: &str,
+        world_x: f32,
+        world_y: f32,
+        size: f32,
+    ) {
+        let screen = gfx.screen_size();
+        let coords = CoordinateSystem::with_default_offset(screen.x, screen.y);
+        let left_x = world_x + coords.center_text_x(text, size, 0.5);
+        let screen_pos = coords.world_to_screen(vec2(left_x, world_y));
+        gfx.text(text)
+            .at(screen_pos)
+            .size(size)
+            .color(COLOR_DARK_GRAY);
     }
 
     /// Draw the game UI behind the board
     /// This should be called BEFORE drawing the grid and pieces
-    pub fn draw(&self, gfx: &mut Graphics, score_manager: &ScoreManager) {
+    /// line_goal: lines needed to finish, shown in place of the line count (sprint, dig)
+    pub fn draw(
+        &self,
+        gfx: &mut Graphics,
+        score_manager: &ScoreManager,
+        line_goal: Option<LineGoal>,
+        pause_keys: &str,
+    ) {
         let screen = gfx.screen_size();
         let screen_height = screen.y;
         
@@ -30,11 +284,13 @@ impl GameUI {
         let lines = score_manager.lines_cleared();
         let multiplier = score_manager.multiplier();
         let combo = score_manager.combo_count();
+        let back_to_back = score_manager.back_to_back_count();
 
-        // Draw "Press Q to quit game" at the top of the screen
+        // Draw "Press P / Esc to pause" (whatever pause is bound to) at the top of the screen
         let quit_y = -450.0 * scale_factor;
         let quit_size = (screen_height * 0.019).max(16.0).min(32.0);
-        self.draw_centered_text(gfx, "Press Q to quit game", quit_y, quit_size, COLOR_DARK_GRAY);
+        let pause_text = format!("Press {} to pause", pause_keys);
+        self.draw_centered_text(gfx, &pause_text, quit_y, quit_size, COLOR_DARK_GRAY);
 
         // Draw large score in the center
         let score_text = format!("{}", score);
@@ -42,21 +298,24 @@ impl GameUI {
         let score_size = (screen_height * 0.115).max(60.0).min(200.0);
         self.draw_centered_text(gfx, &score_text, score_y, score_size, COLOR_TEXT_GREEN);
 
-        // Draw level above score with level multiplier indicator
-        let level_mult = match level {
-            0..=4 => "x1",
-            5..=9 => "x2",
-            10..=14 => "x3",
-            15..=19 => "x5",
-            _ => "x8",
+        // Draw level above score, with the level multiplier indicator under Retris scoring
+        let level_text = match score_manager.scoring_rule() {
+            ScoringRuleKind::Retris => format!(
+                "LEVEL {} (x{})",
+                level,
+                RetrisScoring::level_multiplier(level)
+            ),
+            _ => format!("LEVEL {}", level),
         };
-        let level_text = format!("LEVEL {} ({})", level, level_mult);
         let level_y = -200.0 * scale_factor;
         let level_size = (screen_height * 0.038).max(24.0).min(64.0);
         self.draw_centered_text(gfx, &level_text, level_y, level_size, COLOR_TEXT_GREEN);
 
         // Draw lines below score
-        let lines_text = format!("LINES: {}", lines);
+        let lines_text = match line_goal {
+            Some(goal) => format!("{}: {}/{}", goal.label, goal.cleared, goal.target),
+            None => format!("LINES: {}", lines),
+        };
         let lines_y = 50.0 * scale_factor;
         let lines_size = (screen_height * 0.031).max(20.0).min(48.0);
         self.draw_centered_text(gfx, &lines_text, lines_y, lines_size, COLOR_TEXT_GREEN);
@@ -76,6 +335,66 @@ impl GameUI {
             let combo_size = (screen_height * 0.038).max(24.0).min(64.0);
             self.draw_centered_text(gfx, &combo_text, combo_y, combo_size, COLOR_MAGENTA);
         }
+
+        // Draw back-to-back chain (if active)
+        if back_to_back > 0 {
+            let b2b_text = format!("B2B x{}", back_to_back);
+            let b2b_y = 200.0 * scale_factor;
+            let b2b_size = (screen_height * 0.034).max(22.0).min(52.0);
+            self.draw_centered_text(gfx, &b2b_text, b2b_y, b2b_size, COLOR_CYAN);
+        }
+    }
+
+    /// Draw the run timer under the pause hint, with the personal best to beat under it
+    /// Both stay above where callouts start so they never overlap
+    pub fn draw_timer(&self, gfx: &mut Graphics, millis: u64, best_millis: Option<u64>) {
+        let screen_height = gfx.screen_size().y;
+        let scale = Self::scale_factor(screen_height);
+
+        let timer_size = (screen_height * 0.046).clamp(28.0, 80.0);
+        let timer_text = format_time(millis);
+        self.draw_centered_text(gfx, &timer_text, -410.0 * scale, timer_size, COLOR_TEXT_GREEN);
+
+        if let Some(best) = best_millis {
+            let best_text = format!("BEST {}", format_time(best));
+            let best_size = (screen_height * 0.019).clamp(14.0, 32.0);
+            // Turns orange once this run can no longer beat the best
+            let color = if millis > best {
+                COLOR_ORANGE
+            } else {
+                COLOR_DARK_GRAY
+            };
+            self.draw_centered_text(gfx, &best_text, -355.0 * scale, best_size, color);
+        }
+    }
+
+    /// Draw the time left on the clock under the pause hint, orange for the last 10 seconds
+    pub fn draw_time_left(&self, gfx: &mut Graphics, millis: u64) {
+        let screen_height = gfx.screen_size().y;
+        let scale = Self::scale_factor(screen_height);
+
+        let size = (screen_height * 0.046).clamp(28.0, 80.0);
+        let color = if millis < 10_000 {
+            COLOR_ORANGE
+        } else {
+            COLOR_TEXT_GREEN
+        };
+        self.draw_centered_text(gfx, &format_time(millis), -410.0 * scale, size, color);
+    }
+
+    /// Draw the resume countdown ("3", "2", "1") over the board
+    pub fn draw_countdown(&self, gfx: &mut Graphics, seconds_left: f32) {
+        let screen_height = gfx.screen_size().y;
+        let size = (screen_height * 0.15).clamp(80.0, 260.0);
+        let text = format!("{}", seconds_left.ceil().max(1.0) as u32);
+        self.draw_centered_text(gfx, &text, -size / 2.0, size, COLOR_ORANGE);
+    }
+
+    /// Show replay playback state (speed or paused) just above the board
+    pub fn draw_replay_status(&self, gfx: &mut Graphics, text: &str, grid_top_y: f32) {
+        let screen_height = gfx.screen_size().y;
+        let size = (screen_height * 0.023).clamp(16.0, 40.0);
+        self.draw_centered_text(gfx, text, grid_top_y - size * 1.2, size, COLOR_ORANGE);
     }
 
     /// Helper to draw centered text
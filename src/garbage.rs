@@ -0,0 +1,194 @@
+use crate::board::Board;
+use crate::engine::TICK_DURATION;
+use crate::randomizer::GameRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Most garbage rows a board can start with (leaves room to spawn and move pieces)
+const MAX_STARTING_ROWS: u16 = 16;
+
+/// Garbage the board starts with, and how more of it arrives (dig)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GarbageSettings {
+    /// Garbage rows at the bottom of the board when the game starts (up to 16)
+    pub starting_rows: u16,
+    /// Chance (0.0-1.0) that a row's hole isn't lined up with the hole of the row below it
+    /// 0.0 makes one straight well, 1.0 moves the hole on every row
+    pub messiness: f32,
+    /// Seconds between new garbage rows pushing the stack up (None adds no new garbage)
+    pub rise_interval: Option<f32>,
+}
+
+impl GarbageSettings {
+    /// Short tag for these settings, so personal bests are only compared like for like
+    pub fn storage_key(&self) -> String {
+        format!(
+            "mess{}_rise{}",
+            (self.messiness * 100.0).round() as u32,
+            self.rise_interval.unwrap_or(0.0)
+        )
+    }
+}
+
+/// Puts garbage rows into a board, keeping the holes of neighbouring rows related
+pub struct GarbageGenerator {
+    settings: GarbageSettings,
+    last_hole: Option<i32>, // Hole column of the row added most recently
+    rise_timer: f32,        // Time since the last new row
+}
+
+impl GarbageGenerator {
+    pub fn new(settings: GarbageSettings) -> Self {
+        Self {
+            settings,
+            last_hole: None,
+            rise_timer: 0.0,
+        }
+    }
+
+    /// Fill the bottom of the board with the starting garbage
+    pub fn fill(&mut self, board: &mut Board, rng: &mut GameRng) {
+        for _ in 0..self.settings.starting_rows.min(MAX_STARTING_ROWS) {
+            self.add_row(board, rng);
+        }
+    }
+
+    /// Advance the rise timer by one tick
+    /// Returns true if a new garbage row was pushed in from the bottom
+    pub fn tick(&mut self, board: &mut Board, rng: &mut GameRng) -> bool {
+        let Some(interval) = self.settings.rise_interval else {
+            return false;
+        };
+        self.rise_timer += TICK_DURATION;
+        if self.rise_timer < interval {
+            return false;
+        }
+        self.rise_timer -= interval;
+        self.add_row(board, rng);
+        true
+    }
+
+    /// Push one garbage row in at the bottom of the board
+    fn add_row(&mut self, board: &mut Board, rng: &mut GameRng) {
+        let width = board.width() as i32;
+        let hole = match self.last_hole {
+            // Messy rows move the hole to any other column
+            Some(last) if rng.random::<f32>() < self.settings.messiness => {
+                (last + rng.random_range(1..width)) % width
+            }
+            Some(last) => last,
+            None => rng.random_range(0..width),
+        };
+        board.push_garbage_row(hole);
+        self.last_hole = Some(hole);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::seeded_rng;
+
+    const WIDTH: usize = 10;
+
+    fn settings(starting_rows: u16, messiness: f32) -> GarbageSettings {
+        GarbageSettings {
+            starting_rows,
+            messiness,
+            rise_interval: None,
+        }
+    }
+
+    /// Hole column of each garbage row, from the bottom of the board up
+    fn holes(board: &Board) -> Vec<i32> {
+        let bottom_row = board.cells().map(|(_, y, _)| y).max().unwrap_or(-1);
+        let mut holes = Vec::new();
+        for row in (0..=bottom_row).rev() {
+            let row_holes: Vec<i32> = (0..WIDTH as i32)
+                .filter(|&column| !board.is_cell_occupied(column, row))
+                .collect();
+            if row_holes.len() == WIDTH {
+                break;
+            }
+            assert_eq!(row_holes.len(), 1, "row {row} should have exactly one hole");
+            holes.push(row_holes[0]);
+        }
+        holes
+    }
+
+    fn filled_holes(settings: GarbageSettings, seed: u64) -> Vec<i32> {
+        let mut board = Board::new(WIDTH, 20);
+        GarbageGenerator::new(settings).fill(&mut board, &mut seeded_rng(seed));
+        holes(&board)
+    }
+
+    /// How many rows have their hole in a different column than the row below
+    fn moved_holes(holes: &[i32]) -> usize {
+        holes.windows(2).filter(|pair| pair[0] != pair[1]).count()
+    }
+
+    #[test]
+    fn fill_adds_rows_with_one_hole_each_up_to_the_cap() {
+        assert_eq!(filled_holes(settings(0, 0.3), 1).len(), 0);
+        assert_eq!(filled_holes(settings(9, 0.3), 1).len(), 9);
+        assert_eq!(
+            filled_holes(settings(40, 0.3), 1).len(),
+            MAX_STARTING_ROWS as usize
+        );
+    }
+
+    #[test]
+    fn clean_garbage_keeps_one_straight_well() {
+        for seed in 0..20 {
+            assert_eq!(moved_holes(&filled_holes(settings(16, 0.0), seed)), 0);
+        }
+    }
+
+    #[test]
+    fn fully_messy_garbage_moves_the_hole_every_row() {
+        for seed in 0..20 {
+            assert_eq!(moved_holes(&filled_holes(settings(16, 1.0), seed)), 15);
+        }
+    }
+
+    #[test]
+    fn messiness_is_the_chance_of_the_hole_moving() {
+        let (moved, total) = (0..200).fold((0, 0), |(moved, total), seed| {
+            let holes = filled_holes(settings(16, 0.3), seed);
+            (moved + moved_holes(&holes), total + holes.len() - 1)
+        });
+        let ratio = moved as f32 / total as f32;
+        assert!(
+            (0.25..0.35).contains(&ratio),
+            "hole moved on {ratio} of rows"
+        );
+    }
+
+    #[test]
+    fn rising_garbage_arrives_once_per_interval() {
+        let mut board = Board::new(WIDTH, 20);
+        let mut rng = seeded_rng(3);
+        let mut generator = GarbageGenerator::new(GarbageSettings {
+            rise_interval: Some(0.5),
+            ..settings(0, 0.3)
+        });
+        let added = (0..600)
+            .filter(|_| generator.tick(&mut board, &mut rng))
+            .count();
+        assert!((19..=20).contains(&added), "{added} rows in 10 seconds");
+        assert_eq!(holes(&board).len(), added);
+
+        let mut still = GarbageGenerator::new(settings(0, 0.3));
+        assert!(!(0..600).any(|_| still.tick(&mut board, &mut rng)));
+    }
+
+    #[test]
+    fn storage_key_names_messiness_and_rise() {
+        assert_eq!(settings(9, 0.3).storage_key(), "mess30_rise0");
+        let rising = GarbageSettings {
+            rise_interval: Some(4.0),
+            ..settings(9, 1.0)
+        };
+        assert_eq!(rising.storage_key(), "mess100_rise4");
+    }
+}
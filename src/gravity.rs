@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+/// Gravity is measured per frame at this rate, whatever the actual frame rate is
+pub const FRAMES_PER_SECOND: f32 = 60.0;
+
+/// Gravity at or above this many cells per frame drops pieces straight to the floor (20G)
+pub const TWENTY_G: f32 = 20.0;
+
+/// Fall speed for each level, in (fractional) cells per frame
+/// Levels past the end of the table keep the last entry's speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct GravityTable {
+    cells_per_frame: Vec<f32>,
+}
+
+impl GravityTable {
+    /// Build a table from per-level speeds (level 0 first)
+    pub fn new(cells_per_frame: Vec<f32>) -> Self {
+        assert!(
+            !cells_per_frame.is_empty(),
+            "Gravity table needs at least one level"
+        );
+        Self { cells_per_frame }
+    }
+
+    /// Fall speed at a level, in cells per frame
+    pub fn cells_per_frame(&self, level: u16) -> f32 {
+        let index = (level as usize).min(self.cells_per_frame.len() - 1);
+        self.cells_per_frame[index]
+    }
+}
+
+/// Available gravity curves, picked per mode
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum GravityKind {
+    /// 2 cells per second plus one per level, up to 1G (original Retris)
+    #[default]
+    Retris,
+    /// Guideline curve: (0.8 - (level × 0.007))^level seconds per row, reaching 20G at level 19
+    Guideline,
+    /// NES frames-per-row table, 1G from level 29
+    Nes,
+    /// 20G from the start: pieces land the moment they spawn
+    Instant,
+    /// One cell per second at every level, so speed never changes (Ultra)
+    Fixed,
+}
+
+impl GravityKind {
+    /// Curves the rules screen cycles through (Fixed belongs to Ultra and isn't offered)
+    pub const SELECTABLE: [GravityKind; 4] = [
+        GravityKind::Retris,
+        GravityKind::Guideline,
+        GravityKind::Nes,
+        GravityKind::Instant,
+    ];
+
+    /// Name shown on the rules screen
+    pub fn name(self) -> &'static str {
+        match self {
+            GravityKind::Retris => "Retris",
+            GravityKind::Guideline => "Guideline",
+            GravityKind::Nes => "NES",
+            GravityKind::Instant => "20G",
+            GravityKind::Fixed => "Fixed",
+        }
+    }
+
+    /// Build the per-level table for this curve
+    pub fn table(self) -> GravityTable {
+        match self {
+            GravityKind::Retris => GravityTable::new(
+                (0..=58)
+                    .map(|level| (2 + level) as f32 / FRAMES_PER_SECOND)
+                    .collect(),
+            ),
+            GravityKind::Guideline => GravityTable::new(
+                (0..=19)
+                    .map(|level| {
+                        let seconds_per_row = (0.8 - level as f32 * 0.007).powi(level);
+                        (1.0 / (seconds_per_row * FRAMES_PER_SECOND)).min(TWENTY_G)
+                    })
+                    .collect(),
+            ),
+            GravityKind::Nes => {
+                const FRAMES_PER_ROW: [u8; 30] = [
+                    48, 43, 38, 33, 28, 23, 18, 13, 8, 6, // Levels 0-9
+                    5, 5, 5, 4, 4, 4, 3, 3, 3, 2, // Levels 10-19
+                    2, 2, 2, 2, 2, 2, 2, 2, 2, 1, // Levels 20-29
+                ];
+                GravityTable::new(
+                    FRAMES_PER_ROW
+                        .iter()
+                        .map(|&frames| 1.0 / frames as f32)
+                        .collect(),
+                )
+            }
+            GravityKind::Instant => GravityTable::new(vec![TWENTY_G]),
+            GravityKind::Fixed => GravityTable::new(vec![1.0 / FRAMES_PER_SECOND]),
+        }
+    }
+}
@@ -1,11 +1,13 @@
-use crate::game_data::GameTable;
+use crate::board::{Board, SPAWN_ROWS};
 use crate::retris_colors::*;
-use egor::input::Input;
 use egor::math::{Vec2, vec2};
 use egor::render::{Color, Graphics};
 
-/// Number of hidden rows above the visible playfield where pieces spawn
-pub const SPAWN_ROWS: usize = 4;
+/// Duration of the non-destructive level up flash (in seconds)
+const LEVEL_FLASH_DURATION: f32 = 0.6;
+
+/// Peak opacity of the level up flash overlay
+const LEVEL_FLASH_MAX_ALPHA: f32 = 0.35;
 
 /// Represents a cell that's cascading down during level transition
 struct CascadingCell {
@@ -16,16 +18,17 @@ struct CascadingCell {
     velocity: f32,      // Fall speed in pixels per second
 }
 
+/// Where the board sits on screen, and how its cells are drawn
+/// The cells themselves live in the engine's `Board`
 pub struct Grid {
     position: Vec2,
     width: usize,
-    height: usize,         // Total height including spawn area
     visible_height: usize, // Visible height on screen
     cell_size: f32,
     visible_position: Vec2, // Position of visible area (for drawing border)
-    occupied_cells: GameTable<Color>, // Track which cells are occupied and their colors
     cascading_cells: Vec<CascadingCell>, // Cells that are animating during level transition
     is_cascading: bool,    // True when cascade animation is active
+    flash_timer: f32,      // Seconds left of the level up flash (0 when not flashing)
 }
 
 impl Grid {
@@ -35,11 +38,15 @@ impl Grid {
         width_cells: usize,
         visible_height_cells: usize,
         min_padding: f32,
+        side_panel_width: f32,
     ) -> Self {
         // Step 1: Calculate padding as percentage of screen dimensions (use the smaller dimension for consistency)
         // Use percentage-based padding similar to title screen, but also respect minimum padding
         const PADDING_PERCENT: f32 = 0.05; // 5% padding on each side (total 10% of width/height)
-        let padding_width = (screen_width * PADDING_PERCENT).max(min_padding);
+        // Horizontal padding must also leave room for the side panels (next queue, hold slot)
+        let padding_width = (screen_width * PADDING_PERCENT)
+            .max(min_padding)
+            .max(side_panel_width);
         let padding_height = (screen_height * PADDING_PERCENT).max(min_padding);
         
         // Calculate available space for the grid (screen minus padding on all sides)
@@ -56,10 +63,7 @@ impl Grid {
         let grid_width_pixels = width_cells as f32 * cell_size_pixels;
         let visible_grid_height_pixels = visible_height_cells as f32 * cell_size_pixels;
 
-        // Step 4: Calculate total grid height (visible + spawn area)
-        let total_height_cells = visible_height_cells + SPAWN_ROWS;
-
-        // Step 5: Position the grid in world coordinates
+        // Step 4: Position the grid in world coordinates
         // Coordinate system has (0,0) at center of screen
         // visible_position is the top-left corner of the visible playfield area
         let visible_area_top_left = vec2(
@@ -78,13 +82,12 @@ impl Grid {
         Self {
             position: total_grid_top_left,
             width: width_cells,
-            height: total_height_cells,
             visible_height: visible_height_cells,
             cell_size: cell_size_pixels,
             visible_position: visible_area_top_left,
-            occupied_cells: GameTable::new(width_cells, total_height_cells),
             cascading_cells: Vec::new(),
             is_cascading: false,
+            flash_timer: 0.0,
         }
     }
 
@@ -92,83 +95,19 @@ impl Grid {
         self.cell_size
     }
 
-    pub fn position(&self) -> Vec2 {
-        self.position
-    }
-
     pub fn width_cells(&self) -> usize {
         self.width
     }
 
-    pub fn height_cells(&self) -> usize {
-        self.height
-    }
-
-    pub fn visible_height_cells(&self) -> usize {
-        self.visible_height
-    }
-
     pub fn visible_position(&self) -> Vec2 {
         self.visible_position
     }
 
-    pub fn is_cell_occupied(&self, cell_x: i32, cell_y: i32) -> bool {
-        // Check bounds
-        if cell_x < 0 || cell_x >= self.width as i32 || cell_y < 0 || cell_y >= self.height as i32 {
-            return true; // Out of bounds counts as occupied
-        }
-        self.occupied_cells.has(cell_x, cell_y)
+    pub fn update(&mut self, fixed_delta: f32) {
+        self.flash_timer = (self.flash_timer - fixed_delta).max(0.0);
     }
 
-    pub fn mark_cells_occupied(&mut self, cells: &[(i32, i32, Color)]) {
-        for &(x, y, color) in cells {
-            if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
-                self.occupied_cells.set(x, y, color);
-            } else {
-                println!(
-                    "WARNING: Filtered out out-of-bounds cell: ({}, {}) - bounds: width={}, height={}",
-                    x, y, self.width, self.height
-                );
-            }
-        }
-    }
-
-    pub fn can_move_down(&self, shape_cells: &[(i32, i32)]) -> bool {
-        let has_cells_above_grid = shape_cells.iter().any(|&(_, y)| y < 0);
-        if has_cells_above_grid {
-            return true; // Always allow movement if any cells are above grid
-        }
-
-        // Check if any cell would move into an occupied cell or out of bounds
-        for &(cell_x, cell_y) in shape_cells {
-            let new_y = cell_y + 1;
-
-            // Check if at bottom of grid
-            if new_y >= self.height as i32 {
-                println!(
-                    "can_move_down: false - at bottom (new_y={} >= height={})",
-                    new_y, self.height
-                );
-                return false;
-            }
-
-            // Check if the cell below is occupied
-            if self.occupied_cells.has(cell_x, new_y) {
-                println!(
-                    "can_move_down: false - cell ({}, {}) is occupied",
-                    cell_x, new_y
-                );
-                return false;
-            }
-        }
-        true
-    }
-
-    pub fn update(&mut self, _input: &Input, _fixed_delta: f32) {
-        // Blank for now
-    }
-
-    pub fn draw(&mut self, gfx: &mut Graphics, alpha: f32) {
+    pub fn draw(&mut self, gfx: &mut Graphics, board: &Board, alpha: f32) {
         let grid_width = self.width as f32 * self.cell_size;
         let visible_grid_height = self.visible_height as f32 * self.cell_size;
 
@@ -205,11 +144,20 @@ impl Grid {
             ))
             .color(COLOR_BORDER_GREEN);
 
-        self.draw_occupied_cells(gfx, alpha);
+        self.draw_occupied_cells(gfx, board, alpha);
+
+        // Level up flash over the visible playfield, fading out
+        if self.flash_timer > 0.0 {
+            let flash_alpha = LEVEL_FLASH_MAX_ALPHA * (self.flash_timer / LEVEL_FLASH_DURATION);
+            gfx.rect()
+                .size(vec2(grid_width, visible_grid_height))
+                .at(self.visible_position)
+                .color(Color::new([1.0, 1.0, 1.0, flash_alpha]));
+        }
     }
 
     /// Draw all occupied cells with their stored colors
-    fn draw_occupied_cells(&self, gfx: &mut Graphics, _alpha: f32) {
+    fn draw_occupied_cells(&self, gfx: &mut Graphics, board: &Board, _alpha: f32) {
         const BORDER_WIDTH: f32 = 1.0;
 
         let cell_size_vec = vec2(self.cell_size, self.cell_size);
@@ -238,7 +186,7 @@ impl Grid {
             }
         } else {
             // Normal drawing of occupied cells
-            for (cell_x, cell_y, color) in self.occupied_cells.iter() {
+            for (cell_x, cell_y, shape_index) in board.cells() {
                 let world_pos = vec2(
                     self.position.x + cell_x as f32 * self.cell_size,
                     self.position.y + cell_y as f32 * self.cell_size,
@@ -256,44 +204,46 @@ impl Grid {
                     self.cell_size - BORDER_WIDTH * 2.0,
                 );
                 let fill_pos = world_pos + vec2(BORDER_WIDTH, BORDER_WIDTH);
-                gfx.rect().size(fill_size).at(fill_pos).color(*color);
+                gfx.rect().size(fill_size).at(fill_pos).color(cell_color(shape_index));
             }
         }
     }
 
-    /// Clear completed lines and shift cells above down
-    /// Returns the number of lines cleared
-    pub fn clear_completed_lines(&mut self) -> usize {
-        let mut cleared_count = 0;
-        let mut row_y = (self.height - 1) as i32;
-
-        // Iterate from bottom to top, checking each row
-        // After removing a row, check the same index again (it now contains what was above)
-        while row_y >= SPAWN_ROWS as i32 {
-            // Check if this row is complete
-            if self.occupied_cells.is_row_full(row_y) {
-                if self.occupied_cells.remove_row_and_shift_down(row_y) {
-                    cleared_count += 1;
-                    // Don't decrement row_y - check the same row again
-                    // because the row above has shifted down into this position
-                    continue;
-                }
-            }
-            // Move to the row above
-            row_y -= 1;
+    /// Draw a piece's cells (fill only, like the falling piece)
+    pub fn draw_piece_cells(&self, gfx: &mut Graphics, cells: &[(i32, i32)], color: Color) {
+        const BORDER_WIDTH: f32 = 1.0;
+
+        let fill_size = vec2(
+            self.cell_size - BORDER_WIDTH * 2.0,
+            self.cell_size - BORDER_WIDTH * 2.0,
+        );
+        for &(cell_x, cell_y) in cells {
+            let fill_pos = self.cell_world_position(cell_x, cell_y) + vec2(BORDER_WIDTH, BORDER_WIDTH);
+            gfx.rect().size(fill_size).at(fill_pos).color(color);
         }
+    }
+
+    /// World position of the top-left corner of a cell
+    pub fn cell_world_position(&self, cell_x: i32, cell_y: i32) -> Vec2 {
+        vec2(
+            self.position.x + cell_x as f32 * self.cell_size,
+            self.position.y + cell_y as f32 * self.cell_size,
+        )
+    }
 
-        cleared_count
+    /// World Y position of the bottom edge of the visible playfield
+    pub fn visible_bottom_y(&self) -> f32 {
+        self.visible_position.y + self.visible_height as f32 * self.cell_size
     }
 
-    /// Start the cascade animation for level transition
+    /// Start the cascade animation for level transition with the cells wiped off the board
     /// Each cell gets a different fall velocity based on its column for a cascading effect
-    pub fn start_cascade_animation(&mut self) {
+    pub fn start_cascade_animation(&mut self, wiped_cells: &[(i32, i32, i32)]) {
         self.is_cascading = true;
         self.cascading_cells.clear();
 
-        // Convert all occupied cells to cascading cells
-        for (col, row, color) in self.occupied_cells.iter() {
+        // Convert all wiped cells to cascading cells
+        for &(col, row, shape_index) in wiped_cells {
             // Give each column a different delay/velocity for cascade effect
             // Columns further to the right start falling later (lower velocity initially)
             let base_velocity = 800.0; // Base fall speed in pixels per second
@@ -303,14 +253,11 @@ impl Grid {
             self.cascading_cells.push(CascadingCell {
                 col,
                 row,
-                color: *color,
+                color: cell_color(shape_index),
                 offset_y: 0.0,
                 velocity,
             });
         }
-
-        // Clear the occupied cells table since we've moved them to cascading
-        self.occupied_cells.clear();
     }
 
     /// Update the cascade animation
@@ -338,17 +285,17 @@ impl Grid {
         // Grid is now empty and ready for next level
     }
 
-    /// Check if any cells are occupied in the spawn area (above the visible playfield)
-    /// Returns true if game over condition is met (blocks in spawn area)
-    pub fn has_blocks_in_spawn_area(&self) -> bool {
-        // Check rows 0 to SPAWN_ROWS-1 (the spawn area above visible playfield)
-        for row in 0..(SPAWN_ROWS as i32) {
-            for col in 0..(self.width as i32) {
-                if self.occupied_cells.has(col, row) {
-                    return true;
-                }
-            }
-        }
-        false
+    /// Flash the playfield for a level up without touching the blocks
+    pub fn start_level_flash(&mut self) {
+        self.flash_timer = LEVEL_FLASH_DURATION;
     }
 }
+
+/// Color of a locked cell from the shape index of the piece that left it
+pub fn cell_color(shape_index: i32) -> Color {
+    usize::try_from(shape_index)
+        .ok()
+        .and_then(|index| PIECE_COLORS.get(index))
+        .copied()
+        .unwrap_or(COLOR_DARK_GRAY)
+}
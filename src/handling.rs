@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest DAS the handling screen allows (milliseconds)
+pub const MAX_DAS_MS: u32 = 500;
+
+/// Slowest ARR the handling screen allows (milliseconds per cell)
+pub const MAX_ARR_MS: u32 = 200;
+
+/// Fastest soft drop the handling screen allows (multiple of gravity)
+pub const MAX_SOFT_DROP_FACTOR: u32 = 40;
+
+/// How the piece responds to held inputs - a player preference, not part of the ruleset
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HandlingProfile {
+    /// Delayed auto shift: how long left/right is held before auto-repeat starts (milliseconds)
+    pub das_ms: u32,
+    /// Auto repeat rate: time between auto-repeat moves (milliseconds, 0 = instantly to the wall)
+    pub arr_ms: u32,
+    /// Soft drop speed as a multiple of the current gravity
+    pub soft_drop_factor: u32,
+}
+
+impl Default for HandlingProfile {
+    fn default() -> Self {
+        Self {
+            das_ms: 133,
+            arr_ms: 50,
+            soft_drop_factor: 5,
+        }
+    }
+}
+
+impl HandlingProfile {
+    /// Keep values from a hand-edited or older save within what the screen allows
+    pub fn clamped(self) -> Self {
+        Self {
+            das_ms: self.das_ms.min(MAX_DAS_MS),
+            arr_ms: self.arr_ms.min(MAX_ARR_MS),
+            soft_drop_factor: self.soft_drop_factor.clamp(1, MAX_SOFT_DROP_FACTOR),
+        }
+    }
+
+    /// DAS in seconds
+    pub fn das(&self) -> f32 {
+        self.das_ms as f32 / 1000.0
+    }
+
+    /// Seconds between auto-repeat moves (0 = instant)
+    pub fn arr(&self) -> f32 {
+        self.arr_ms as f32 / 1000.0
+    }
+}
@@ -0,0 +1,243 @@
+use crate::coordinate_system::CoordinateSystem;
+use crate::handling::{HandlingProfile, MAX_ARR_MS, MAX_DAS_MS, MAX_SOFT_DROP_FACTOR};
+use crate::retris_colors::*;
+use crate::retris_ui::Button;
+use crate::storage::Storage;
+use egor::input::{Input, KeyCode};
+use egor::math::vec2;
+use egor::render::Graphics;
+
+/// DAS change per click (milliseconds)
+const DAS_STEP_MS: u32 = 10;
+
+/// ARR change per click (milliseconds)
+const ARR_STEP_MS: u32 = 5;
+
+/// One adjustable handling value
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Setting {
+    Das,
+    Arr,
+    SoftDrop,
+}
+
+impl Setting {
+    const ALL: [Setting; 3] = [Setting::Das, Setting::Arr, Setting::SoftDrop];
+
+    /// Text shown between the - and + buttons
+    fn label(self, handling: &HandlingProfile) -> String {
+        match self {
+            Setting::Das => format!("DAS: {} ms", handling.das_ms),
+            Setting::Arr if handling.arr_ms == 0 => "ARR: 0 ms (instant)".to_string(),
+            Setting::Arr => format!("ARR: {} ms", handling.arr_ms),
+            Setting::SoftDrop => format!("Soft Drop: x{}", handling.soft_drop_factor),
+        }
+    }
+
+    /// Step the value up (+1) or down (-1), staying within its limits
+    fn adjust(self, handling: &mut HandlingProfile, steps: i32) {
+        let step = |value: u32, step: u32, min: u32, max: u32| {
+            (value as i64 + steps as i64 * step as i64).clamp(min as i64, max as i64) as u32
+        };
+        match self {
+            Setting::Das => handling.das_ms = step(handling.das_ms, DAS_STEP_MS, 0, MAX_DAS_MS),
+            Setting::Arr => handling.arr_ms = step(handling.arr_ms, ARR_STEP_MS, 0, MAX_ARR_MS),
+            Setting::SoftDrop => {
+                handling.soft_drop_factor =
+                    step(handling.soft_drop_factor, 1, 1, MAX_SOFT_DROP_FACTOR)
+            }
+        }
+    }
+}
+
+/// DAS, ARR and soft drop speed, each with - / + buttons
+/// Changes are saved as soon as they're made
+pub struct HandlingScreen {
+    setting_buttons: Vec<(Setting, Button, Button)>, // Setting with its - and + buttons
+    reset_button: Button,
+    back_button: Button,
+    handling: HandlingProfile,
+}
+
+impl HandlingScreen {
+    /// Scale factor based on screen height, clamped to prevent extreme sizes
+    fn scale_factor(screen_height: f32) -> f32 {
+        (screen_height / 1048.0).clamp(0.5, 2.0)
+    }
+
+    /// Base row width, from the - button to the + button (normalized to 1048px height)
+    const BASE_ROW_WIDTH: f32 = 400.0;
+    /// Base size of the - / + buttons (normalized to 1048px height)
+    const BASE_STEP_BUTTON_SIZE: f32 = 50.0;
+    /// Base spacing between rows (normalized to 1048px height)
+    const BASE_ROW_STEP: f32 = 90.0;
+    /// Base Y position of the first row (normalized to 1048px height)
+    const BASE_FIRST_ROW_Y: f32 = -150.0;
+    /// Base Y position of the Reset / Back buttons (normalized to 1048px height)
+    const BASE_BUTTONS_Y: f32 = 170.0;
+    /// Base button height (normalized to 1048px height)
+    const BASE_BUTTON_HEIGHT: f32 = 50.0;
+    /// Base gap between the Reset and Back buttons (normalized to 1048px height)
+    const BASE_BUTTON_GAP: f32 = 20.0;
+
+    pub fn new() -> Self {
+        let mut screen = Self {
+            setting_buttons: Setting::ALL
+                .iter()
+                .map(|&setting| {
+                    (
+                        setting,
+                        Button::new(0.0, 0.0, 0.0, 0.0, "-"),
+                        Button::new(0.0, 0.0, 0.0, 0.0, "+"),
+                    )
+                })
+                .collect(),
+            reset_button: Button::new(0.0, 0.0, 0.0, 0.0, "Reset"),
+            back_button: Button::new(0.0, 0.0, 0.0, 0.0, "Back"),
+            handling: Storage::load_handling(),
+        };
+        // Use default screen dimensions for initial layout (will be updated via update)
+        screen.update(640.0, 1048.0);
+        screen
+    }
+
+    /// Reload the profile when the screen is opened
+    pub fn open(&mut self) {
+        self.handling = Storage::load_handling();
+    }
+
+    /// Update button positions and sizes based on actual screen dimensions
+    pub fn update(&mut self, _screen_width: f32, screen_height: f32) {
+        let scale = Self::scale_factor(screen_height);
+        let width = Self::BASE_ROW_WIDTH * scale;
+        let button_size = Self::BASE_STEP_BUTTON_SIZE * scale;
+        let left_x = -width / 2.0;
+
+        for (index, (_, minus_button, plus_button)) in self.setting_buttons.iter_mut().enumerate() {
+            let row_y = (Self::BASE_FIRST_ROW_Y + Self::BASE_ROW_STEP * index as f32) * scale;
+            minus_button.set_position(left_x, row_y, button_size, button_size);
+            plus_button.set_position(
+                left_x + width - button_size,
+                row_y,
+                button_size,
+                button_size,
+            );
+        }
+
+        // Reset and Back side by side under the rows
+        let button_height = Self::BASE_BUTTON_HEIGHT * scale;
+        let half_width = (width - Self::BASE_BUTTON_GAP * scale) / 2.0;
+        let buttons_y = Self::BASE_BUTTONS_Y * scale;
+        self.reset_button
+            .set_position(left_x, buttons_y, half_width, button_height);
+        self.back_button.set_position(
+            left_x + width - half_width,
+            buttons_y,
+            half_width,
+            button_height,
+        );
+    }
+
+    /// Handle input for the handling screen
+    /// Returns true when the player leaves the screen
+    pub fn handle_input(&mut self, input: &Input, screen_width: f32, screen_height: f32) -> bool {
+        let mut changed = false;
+        for (setting, minus_button, plus_button) in &self.setting_buttons {
+            if minus_button.is_clicked(input, screen_width, screen_height) {
+                setting.adjust(&mut self.handling, -1);
+                changed = true;
+            } else if plus_button.is_clicked(input, screen_width, screen_height) {
+                setting.adjust(&mut self.handling, 1);
+                changed = true;
+            }
+        }
+
+        if self
+            .reset_button
+            .is_clicked(input, screen_width, screen_height)
+        {
+            self.handling = HandlingProfile::default();
+            changed = true;
+        }
+
+        if changed {
+            Storage::save_handling(&self.handling);
+            println!(
+                "Handling: DAS {} ms, ARR {} ms, soft drop x{}",
+                self.handling.das_ms, self.handling.arr_ms, self.handling.soft_drop_factor
+            );
+            return false;
+        }
+
+        input.key_pressed(KeyCode::Escape)
+            || self
+                .back_button
+                .is_clicked(input, screen_width, screen_height)
+    }
+
+    pub fn draw(&self, gfx: &mut Graphics, screen_width: f32, screen_height: f32) {
+        let coords = CoordinateSystem::with_default_offset(screen_width, screen_height);
+        let scale = Self::scale_factor(screen_height);
+
+        // Same backdrop as the settings screen
+        gfx.rect()
+            .at(coords.top_left_world())
+            .size(vec2(screen_width, screen_height))
+            .color(COLOR_DARK_GRAY);
+
+        let title_size = (screen_height * 0.046).clamp(32.0, 80.0);
+        Self::draw_centered_text(gfx, &coords, "HANDLING", -260.0 * scale, title_size);
+
+        // Value labels sit between each row's - and + buttons, vertically centered
+        let label_size = (screen_height * 0.023).clamp(18.0, 40.0);
+        let button_size = Self::BASE_STEP_BUTTON_SIZE * scale;
+        for (index, (setting, minus_button, plus_button)) in self.setting_buttons.iter().enumerate()
+        {
+            let row_y = (Self::BASE_FIRST_ROW_Y + Self::BASE_ROW_STEP * index as f32) * scale;
+            let label = setting.label(&self.handling);
+            Self::draw_centered_text(
+                gfx,
+                &coords,
+                &label,
+                row_y + (button_size - label_size) / 2.0,
+                label_size,
+            );
+            minus_button.draw(gfx, screen_width, screen_height);
+            plus_button.draw(gfx, screen_width, screen_height);
+        }
+
+        self.reset_button.draw(gfx, screen_width, screen_height);
+        self.back_button.draw(gfx, screen_width, screen_height);
+
+        let hint_size = (screen_height * 0.018).clamp(12.0, 36.0);
+        Self::draw_centered_text(
+            gfx,
+            &coords,
+            "ARR 0 moves pieces straight to the wall",
+            260.0 * scale,
+            hint_size,
+        );
+    }
+
+    /// Draw a line of text centered horizontally at a world Y position
+    fn draw_centered_text(
+        gfx: &mut Graphics,
+        coords: &CoordinateSystem,
+        text: &str,
+        world_y: f32,
+        size: f32,
+    ) {
+        let world_x = coords.center_text_x(text, size, 0.5);
+        let screen_pos = coords.world_to_screen(vec2(world_x, world_y));
+        gfx.text(text)
+            .at(screen_pos)
+            .size(size)
+            .color(COLOR_TEXT_GREEN);
+    }
+}
+
+impl Default for HandlingScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
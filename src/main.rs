@@ -1,16 +1,33 @@
 mod background;
 mod background_task;
+mod board;
+mod controls;
+mod controls_screen;
 mod coordinate_system;
 mod debug;
+mod engine;
 mod game;
 mod game_data;
+mod game_mode;
 mod game_over_screen;
 mod game_ui;
+mod garbage;
+mod gravity;
 mod grid;
+mod handling;
+mod handling_screen;
 mod logger;
 mod music_manager;
+mod pause_menu;
+mod randomizer;
+mod replay;
 mod retris_colors;
 mod retris_ui;
+mod rotation;
+mod rule_options;
+mod rules_screen;
+mod ruleset;
+mod scoring;
 mod sound_manager;
 mod storage;
 mod tetris_mobile_controller;
@@ -20,18 +37,23 @@ mod volume_control_screen;
 mod volume_manager;
 
 use background::Background;
+use controls::Action;
+use controls_screen::ControlsScreen;
 use debug::DebugOverlay;
 use egor::app::*;
-use egor::input::{KeyCode, MouseButton};
 use game::Game;
 use game_over_screen::{GameOverAction, GameOverScreen};
+use handling_screen::HandlingScreen;
 use music_manager::MusicManager;
+use pause_menu::{PauseAction, PauseMenu};
 use retris_ui::MuteButton;
+use rules_screen::RulesScreen;
+use ruleset::Ruleset;
 use sound_manager::SoundManager;
 #[cfg(target_arch = "wasm32")]
 use std::sync::atomic::{AtomicBool, Ordering};
 use title_screen::TitleScreen;
-use volume_control_screen::VolumeControlScreen;
+use volume_control_screen::{SettingsAction, VolumeControlScreen};
 use volume_manager::VolumeManager;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
@@ -52,6 +74,53 @@ pub fn start_music() {
     START_MUSIC_FLAG.store(true, Ordering::Relaxed);
 }
 
+// Set by the `visibilitychange` listener when the page gets hidden
+// Frames stop while the tab is in the background, so the flag is picked up on the next frame
+#[cfg(target_arch = "wasm32")]
+static PAGE_HIDDEN_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Listen to the Page Visibility API so switching tabs pauses the game
+#[cfg(target_arch = "wasm32")]
+fn watch_page_visibility() {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    let on_visibility_change = Closure::<dyn FnMut()>::new(|| {
+        let hidden = web_sys::window()
+            .and_then(|w| w.document())
+            .is_some_and(|d| d.hidden());
+        if hidden {
+            PAGE_HIDDEN_FLAG.store(true, Ordering::Relaxed);
+        }
+    });
+    if document
+        .add_event_listener_with_callback(
+            "visibilitychange",
+            on_visibility_change.as_ref().unchecked_ref(),
+        )
+        .is_err()
+    {
+        eprintln!("Failed to watch page visibility");
+    }
+    // The listener lives for the whole app
+    on_visibility_change.forget();
+}
+
+/// Check if the page is hidden, or was hidden at some point since the last frame
+#[cfg(target_arch = "wasm32")]
+fn page_hidden() -> bool {
+    let was_hidden = PAGE_HIDDEN_FLAG.swap(false, Ordering::Relaxed);
+    let is_hidden = web_sys::window()
+        .and_then(|w| w.document())
+        .is_some_and(|d| d.hidden());
+    was_hidden || is_hidden
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn page_hidden() -> bool {
+    false // No page visibility outside the browser, window focus covers it
+}
+
 /// Get the device pixel ratio directly from the browser
 #[cfg(target_arch = "wasm32")]
 pub fn get_device_pixel_ratio() -> f32 {
@@ -72,6 +141,25 @@ pub fn is_debug_enabled() -> bool {
     DEBUG_ENABLED
 }
 
+/// Seed requested for new games, if any
+/// Native builds read `RETRIS_SEED` so a shared run can be replayed exactly
+#[cfg(not(target_arch = "wasm32"))]
+fn requested_seed() -> Option<u64> {
+    std::env::var("RETRIS_SEED").ok()?.parse().ok()
+}
+
+/// Web builds read a `?seed=` query parameter from the page URL instead
+#[cfg(target_arch = "wasm32")]
+fn requested_seed() -> Option<u64> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("seed="))?
+        .parse()
+        .ok()
+}
+
 /// Helper function to create audio managers
 /// This should only be called after user interaction in WASM
 fn create_audio_managers(
@@ -166,6 +254,9 @@ enum GameState {
     Title,
     Playing,
     VolumeControl,
+    Controls,
+    Handling,
+    Rules,
     GameOver,
 }
 
@@ -183,8 +274,12 @@ fn main() {
 
     let mut title_screen = TitleScreen::new();
     let mut game: Option<Game> = None;
+    // Rules of the mode picked on the title screen, reused on retry
+    let mut ruleset = Ruleset::default();
     let mut background = Background::new(100);
     let mut was_focused = true;
+    let mut window_focused = true; // Last focus state reported by the window
+    let mut auto_paused = false; // Track if the game was paused because we lost focus
     let mut unfocused_timer: Option<f32> = None;
     let mut muted_due_to_unfocused = false; // Track if we muted due to unfocused timeout
     const UNFOCUSED_MUTE_DELAY: f32 = 15.0; // seconds
@@ -223,9 +318,22 @@ fn main() {
     let mut previous_state = GameState::Title; // Track state before opening volume control
     let mut was_in_volume_control = false; // Track if we were in volume control last frame
 
+    // Create controls screen (opened from settings) and load the bindings it edits
+    let mut controls_screen = ControlsScreen::new();
+    let mut bindings = storage::Storage::load_control_bindings();
+
+    // Create handling screen (DAS / ARR / soft drop, opened from settings)
+    let mut handling_screen = HandlingScreen::new();
+
+    // Create rules screen (randomizer and other rule options, opened from settings)
+    let mut rules_screen = RulesScreen::new();
+
     // Create game over screen
     let mut game_over_screen = GameOverScreen::new();
 
+    // Create pause menu
+    let mut pause_menu = PauseMenu::new();
+
     // Create debug overlay
     let mut debug_overlay = DebugOverlay::new();
 
@@ -236,20 +344,22 @@ fn main() {
         }
     }
 
+    #[cfg(target_arch = "wasm32")]
+    watch_page_visibility();
+
     App::new()
         .title("Retris")
         .screen_size(568, 1136)
         .max_surface_size(640, 1136)
         .vsync(true)
         .run(move |egor, timer| {
-            let mut is_focused = true;
             let gfx = &mut egor.gfx;
             let input = egor.input;
 
             for x in &egor.events {
                 match x {
                     EgorEvent::Focused(focused) => {
-                        is_focused = *focused;
+                        window_focused = *focused;
                     }
                     EgorEvent::CloseRequested => {
                         println!("bye! we closed")
@@ -258,6 +368,26 @@ fn main() {
                 }
             }
 
+            // A hidden page counts as unfocused even if the window still has focus
+            let is_focused = window_focused && !page_hidden();
+
+            // Pause an active game the moment focus is lost, before it gets another update
+            // When focus comes back, a game we paused ourselves resumes after a countdown
+            if is_focused != was_focused && state == GameState::Playing {
+                if let Some(ref mut g) = game {
+                    if !is_focused && !g.is_paused() {
+                        g.pause();
+                        pause_menu.open();
+                        auto_paused = true;
+                    } else if is_focused && auto_paused && g.is_paused() {
+                        g.resume_with_countdown();
+                    }
+                }
+            }
+            if is_focused {
+                auto_paused = false;
+            }
+
             // Check if JavaScript requested to start music/audio (only once)
             // This is when we initialize the audio managers in WASM
             #[cfg(target_arch = "wasm32")]
@@ -333,20 +463,20 @@ fn main() {
                     music_manager.update();
 
                     // Play sounds for title screen interactions
-                    if input.key_pressed(KeyCode::ArrowLeft)
-                        || input.key_pressed(KeyCode::ArrowRight)
-                        || input.key_pressed(KeyCode::ArrowDown)
+                    if bindings.pressed(Action::MoveLeft, input, None)
+                        || bindings.pressed(Action::MoveRight, input, None)
+                        || bindings.pressed(Action::SoftDrop, input, None)
                     {
                         sound_manager.play_bounce();
                     }
-                    if input.key_pressed(KeyCode::Space) {
+                    if bindings.pressed(Action::HardDrop, input, None) {
                         sound_manager.play_shuffle();
                     }
 
-                    title_screen.update(input, timer.delta);
+                    let mode_clicked = title_screen.update(input, &bindings, timer.delta);
                     let screen = gfx.screen_size();
                     title_screen.update_screen_size(screen.x, screen.y);
-                    title_screen.draw(gfx, timer.delta);
+                    title_screen.draw(gfx, &bindings, timer.delta);
                     volume_button.update(gfx);
                     volume_button.draw(gfx);
 
@@ -356,10 +486,18 @@ fn main() {
                         was_in_volume_control = false; // Will be set to true when we enter VolumeControl state
                     }
 
-                    // Check for Enter key to start game
-                    if input.key_pressed(KeyCode::Enter) || input.mouse_pressed(MouseButton::Left) {
+                    // Start the game (Enter or a click by default, except on the mode buttons)
+                    if !mode_clicked && bindings.pressed(Action::Start, input, None) {
                         let screen = gfx.screen_size();
-                        game = Some(Game::new(screen.x, screen.y));
+                        ruleset = title_screen
+                            .mode()
+                            .ruleset(&storage::Storage::load_rule_options());
+                        game = Some(Game::new(
+                            screen.x,
+                            screen.y,
+                            ruleset.clone(),
+                            requested_seed(),
+                        ));
                         state = GameState::Playing;
                     }
                 }
@@ -367,28 +505,66 @@ fn main() {
                     // Update music (check for song transitions)
                     music_manager.update();
 
+                    let mut pause_action = PauseAction::None;
                     if let Some(ref mut g) = game {
-                        sound_manager.update_game(input, timer.delta, g);
-                        g.draw(gfx, timer.delta);
-
-                        // Check for game over condition
-                        if g.is_gameover {
-                            // Save high score if this is a new record
-                            let current_score = g.score_manager().score();
-                            let high_score = g.score_manager().high_score();
-                            if current_score > high_score {
-                                // Save to storage
-                                use crate::storage::{GameData, Storage};
-                                Storage::save_game_data(&GameData {
-                                    high_score: current_score,
-                                });
-                                // Update high score in score manager
-                                g.score_manager_mut().set_high_score(current_score);
+                        if g.is_paused() {
+                            // Board stays hidden, the pause menu takes over
+                            let screen = gfx.screen_size();
+                            g.draw(gfx, timer.delta);
+                            pause_menu.update(screen.x, screen.y);
+                            pause_action =
+                                pause_menu.handle_input(input, &bindings, screen.x, screen.y);
+                            pause_menu.draw(gfx, g.score_manager(), screen.x, screen.y);
+                        } else {
+                            sound_manager.update_game(input, timer.delta, g);
+                            g.draw(gfx, timer.delta);
+
+                            // Check for game over condition
+                            if g.is_gameover {
+                                // Play game over song (stops other music)
+                                music_manager.play_game_over_song();
+                                state = GameState::GameOver;
+                            } else if g.action_pressed(Action::Pause, input) {
+                                // Pause on P, Escape, or mobile pause button (by default)
+                                g.pause();
+                                pause_menu.open();
+                            } else if g.action_pressed(Action::Restart, input) {
+                                // Restart and quit shortcuts pause first and ask for confirmation
+                                g.pause();
+                                pause_menu.request_confirmation(PauseAction::Restart);
+                            } else if g.action_pressed(Action::Quit, input) {
+                                g.pause();
+                                pause_menu.request_confirmation(PauseAction::Quit);
+                            }
+                        }
+                    }
+
+                    match pause_action {
+                        PauseAction::Resume => {
+                            if let Some(ref mut g) = game {
+                                g.resume();
                             }
-                            // Play game over song (stops other music)
-                            music_manager.play_game_over_song();
-                            state = GameState::GameOver;
                         }
+                        PauseAction::Restart => {
+                            let screen = gfx.screen_size();
+                            game = Some(Game::new(
+                                screen.x,
+                                screen.y,
+                                ruleset.clone(),
+                                requested_seed(),
+                            ));
+                        }
+                        PauseAction::Settings => {
+                            previous_state = GameState::Playing;
+                            state = GameState::VolumeControl;
+                            was_in_volume_control = false; // Will be set to true when we enter VolumeControl state
+                        }
+                        PauseAction::Quit => {
+                            game = None;
+                            title_screen.refresh_high_score();
+                            state = GameState::Title;
+                        }
+                        PauseAction::None => {}
                     }
 
                     // Update button positions based on screen dimensions
@@ -397,32 +573,16 @@ fn main() {
                     // Draw volume control button in bottom left
                     volume_button.draw(gfx);
 
-                    // Handle volume button click
+                    // Handle volume button click (the game stays paused while settings are open)
                     if volume_button.is_clicked(input, gfx) {
+                        if let Some(g) = game.as_mut().filter(|g| !g.is_paused()) {
+                            g.pause();
+                            pause_menu.open();
+                        }
                         previous_state = GameState::Playing;
                         state = GameState::VolumeControl;
                         was_in_volume_control = false; // Will be set to true when we enter VolumeControl state
                     }
-
-                    // Restart on R key
-                    if input.key_pressed(KeyCode::KeyR) {
-                        let screen = gfx.screen_size();
-                        game = Some(Game::new(screen.x, screen.y));
-                    }
-
-                    // Return to title on Escape, Q key, or mobile quit button
-                    if input.key_pressed(KeyCode::Escape) || input.key_pressed(KeyCode::KeyQ) {
-                        game = None;
-                        state = GameState::Title;
-                    }
-
-                    // Check mobile controller quit button
-                    if let Some(ref g) = game {
-                        if g.mobile_quit_pressed() {
-                            game = None;
-                            state = GameState::Title;
-                        }
-                    }
                 }
                 GameState::GameOver => {
                     // Update music (check for song transitions)
@@ -440,15 +600,33 @@ fn main() {
                             // Resume regular playlist when returning to menu (will check muted internally)
                             music_manager.start();
                             game = None;
+                            title_screen.refresh_high_score();
                             state = GameState::Title;
                         }
                         GameOverAction::Retry => {
                             // Resume regular playlist when retrying (will check muted internally)
                             music_manager.start();
                             let screen = gfx.screen_size();
-                            game = Some(Game::new(screen.x, screen.y));
+                            game = Some(Game::new(
+                                screen.x,
+                                screen.y,
+                                ruleset.clone(),
+                                requested_seed(),
+                            ));
                             state = GameState::Playing;
                         }
+                        GameOverAction::WatchReplay => {
+                            // The game that just ended was saved as the last replay
+                            match storage::Storage::load_replay() {
+                                Some(replay) => {
+                                    music_manager.start();
+                                    let screen = gfx.screen_size();
+                                    game = Some(Game::from_replay(screen.x, screen.y, replay));
+                                    state = GameState::Playing;
+                                }
+                                None => println!("No replay to watch"),
+                            }
+                        }
                         GameOverAction::None => {
                             // Continue showing game over screen
                         }
@@ -457,7 +635,15 @@ fn main() {
                     // Draw game over screen with score details
                     if let Some(ref g) = game {
                         let screen = gfx.screen_size();
-                        game_over_screen.draw(gfx, g.score_manager(), screen.x, screen.y);
+                        game_over_screen.draw(
+                            gfx,
+                            g.score_manager(),
+                            g.seed(),
+                            g.game_end(),
+                            g.run_time(),
+                            screen.x,
+                            screen.y,
+                        );
                     }
                 }
                 GameState::VolumeControl => {
@@ -483,7 +669,7 @@ fn main() {
                     mute_button_small.draw(gfx);
                     if let Some(ref mut music_mgr) = music_manager.get_mut() {
                         if let Some(ref mut sound_mgr) = sound_manager.as_mut() {
-                            if volume_control_screen.update(
+                            match volume_control_screen.update(
                                 timer.delta,
                                 input,
                                 music_mgr,
@@ -492,14 +678,75 @@ fn main() {
                                 screen.x,
                                 screen.y,
                             ) {
-                                // Close button clicked - unload test sound before leaving
-                                music_mgr.unload_test_sound();
-                                was_in_volume_control = false;
-                                state = previous_state;
+                                SettingsAction::Close => {
+                                    // Close button clicked - unload test sound before leaving
+                                    music_mgr.unload_test_sound();
+                                    was_in_volume_control = false;
+                                    // Apply changed gameplay settings to the running game
+                                    if let Some(ref mut g) = game {
+                                        let settings = storage::Storage::load_gameplay_settings();
+                                        g.set_show_ghost(settings.show_ghost);
+                                    }
+                                    state = previous_state;
+                                }
+                                SettingsAction::Controls => {
+                                    controls_screen.open();
+                                    state = GameState::Controls;
+                                }
+                                SettingsAction::Handling => {
+                                    handling_screen.open();
+                                    state = GameState::Handling;
+                                }
+                                SettingsAction::Rules => {
+                                    rules_screen.open();
+                                    state = GameState::Rules;
+                                }
+                                SettingsAction::None => {}
                             }
                         }
                     }
                 }
+                GameState::Controls => {
+                    let screen = gfx.screen_size();
+                    controls_screen.update(screen.x, screen.y);
+                    let done = controls_screen.handle_input(input, screen.x, screen.y);
+                    controls_screen.draw(gfx, screen.x, screen.y);
+
+                    if done {
+                        // Back to settings with the new bindings applied everywhere
+                        bindings = storage::Storage::load_control_bindings();
+                        if let Some(ref mut g) = game {
+                            g.set_bindings(bindings.clone());
+                        }
+                        state = GameState::VolumeControl;
+                    }
+                }
+                GameState::Handling => {
+                    let screen = gfx.screen_size();
+                    handling_screen.update(screen.x, screen.y);
+                    let done = handling_screen.handle_input(input, screen.x, screen.y);
+                    handling_screen.draw(gfx, screen.x, screen.y);
+
+                    if done {
+                        // Back to settings, a paused game picks up the new handling
+                        if let Some(ref mut g) = game {
+                            g.set_handling(storage::Storage::load_handling());
+                        }
+                        state = GameState::VolumeControl;
+                    }
+                }
+                GameState::Rules => {
+                    let screen = gfx.screen_size();
+                    rules_screen.update(screen.x, screen.y);
+                    let done = rules_screen.handle_input(input, screen.x, screen.y);
+                    rules_screen.draw(gfx, screen.x, screen.y);
+
+                    if done {
+                        // Back to settings, the new rules are used from the next game
+                        title_screen.refresh_high_score();
+                        state = GameState::VolumeControl;
+                    }
+                }
             }
             if is_focused != was_focused {
                 if !is_focused {
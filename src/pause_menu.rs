@@ -0,0 +1,229 @@
+use crate::controls::{Action, ControlBindings};
+use crate::coordinate_system::CoordinateSystem;
+use crate::game_data::ScoreManager;
+use crate::retris_colors::*;
+use crate::retris_ui::Button;
+use egor::input::Input;
+use egor::math::vec2;
+use egor::render::Graphics;
+
+/// Overlay shown while a game is paused
+/// Restart and Quit throw the current game away, so they ask for confirmation first
+pub struct PauseMenu {
+    resume_button: Button,
+    restart_button: Button,
+    settings_button: Button,
+    quit_button: Button,
+    confirm_yes_button: Button,
+    confirm_no_button: Button,
+    confirming: Option<PauseAction>, // Restart or Quit waiting for a yes/no
+}
+
+impl PauseMenu {
+    /// Scale factor based on screen height, clamped to prevent extreme sizes
+    fn scale_factor(screen_height: f32) -> f32 {
+        (screen_height / 1048.0).clamp(0.5, 2.0)
+    }
+
+    /// Base button width (normalized to 1048px height)
+    const BASE_BUTTON_WIDTH: f32 = 220.0;
+    /// Base button height (normalized to 1048px height)
+    const BASE_BUTTON_HEIGHT: f32 = 50.0;
+    /// Base spacing between stacked buttons (normalized to 1048px height)
+    const BASE_BUTTON_SPACING: f32 = 20.0;
+    /// Base Y position of the first button (normalized to 1048px height)
+    const BASE_FIRST_BUTTON_Y: f32 = -100.0;
+
+    pub fn new() -> Self {
+        let mut menu = Self {
+            resume_button: Button::new(0.0, 0.0, 0.0, 0.0, "Resume"),
+            restart_button: Button::new(0.0, 0.0, 0.0, 0.0, "Restart"),
+            settings_button: Button::new(0.0, 0.0, 0.0, 0.0, "Settings"),
+            quit_button: Button::new(0.0, 0.0, 0.0, 0.0, "Quit"),
+            confirm_yes_button: Button::new(0.0, 0.0, 0.0, 0.0, "Yes"),
+            confirm_no_button: Button::new(0.0, 0.0, 0.0, 0.0, "No"),
+            confirming: None,
+        };
+        // Use default screen dimensions for initial layout (will be updated via update)
+        menu.update(640.0, 1048.0);
+        menu
+    }
+
+    /// Reset the menu when the game gets paused
+    pub fn open(&mut self) {
+        self.confirming = None;
+    }
+
+    /// Jump straight to the confirmation for Restart or Quit (keyboard shortcuts)
+    pub fn request_confirmation(&mut self, action: PauseAction) {
+        if matches!(action, PauseAction::Restart | PauseAction::Quit) {
+            self.confirming = Some(action);
+        }
+    }
+
+    /// Update button positions and sizes based on actual screen dimensions
+    pub fn update(&mut self, _screen_width: f32, screen_height: f32) {
+        let scale = Self::scale_factor(screen_height);
+        let width = Self::BASE_BUTTON_WIDTH * scale;
+        let height = Self::BASE_BUTTON_HEIGHT * scale;
+        let step = height + Self::BASE_BUTTON_SPACING * scale;
+        let left_x = -width / 2.0;
+        let first_y = Self::BASE_FIRST_BUTTON_Y * scale;
+
+        // Menu buttons stacked vertically, centered horizontally
+        self.resume_button
+            .set_position(left_x, first_y, width, height);
+        self.restart_button
+            .set_position(left_x, first_y + step, width, height);
+        self.settings_button
+            .set_position(left_x, first_y + step * 2.0, width, height);
+        self.quit_button
+            .set_position(left_x, first_y + step * 3.0, width, height);
+
+        // Yes / No side by side under the confirmation question
+        let half_width = width / 2.0 - Self::BASE_BUTTON_SPACING * scale / 2.0;
+        self.confirm_yes_button
+            .set_position(left_x, first_y + step, half_width, height);
+        self.confirm_no_button.set_position(
+            left_x + width - half_width,
+            first_y + step,
+            half_width,
+            height,
+        );
+    }
+
+    /// Handle input for the pause menu
+    /// Restart and Quit are only returned once confirmed
+    pub fn handle_input(
+        &mut self,
+        input: &Input,
+        bindings: &ControlBindings,
+        screen_width: f32,
+        screen_height: f32,
+    ) -> PauseAction {
+        if let Some(action) = self.confirming {
+            if bindings.pressed(Action::Confirm, input, None)
+                || self
+                    .confirm_yes_button
+                    .is_clicked(input, screen_width, screen_height)
+            {
+                self.confirming = None;
+                return action;
+            }
+            if bindings.pressed(Action::Cancel, input, None)
+                || self
+                    .confirm_no_button
+                    .is_clicked(input, screen_width, screen_height)
+            {
+                self.confirming = None;
+            }
+            return PauseAction::None;
+        }
+
+        if bindings.pressed(Action::Pause, input, None)
+            || bindings.pressed(Action::Cancel, input, None)
+            || bindings.pressed(Action::Confirm, input, None)
+            || self
+                .resume_button
+                .is_clicked(input, screen_width, screen_height)
+        {
+            PauseAction::Resume
+        } else if bindings.pressed(Action::Restart, input, None)
+            || self
+                .restart_button
+                .is_clicked(input, screen_width, screen_height)
+        {
+            self.confirming = Some(PauseAction::Restart);
+            PauseAction::None
+        } else if self
+            .settings_button
+            .is_clicked(input, screen_width, screen_height)
+        {
+            PauseAction::Settings
+        } else if bindings.pressed(Action::Quit, input, None)
+            || self
+                .quit_button
+                .is_clicked(input, screen_width, screen_height)
+        {
+            self.confirming = Some(PauseAction::Quit);
+            PauseAction::None
+        } else {
+            PauseAction::None
+        }
+    }
+
+    pub fn draw(
+        &self,
+        gfx: &mut Graphics,
+        score_manager: &ScoreManager,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let coords = CoordinateSystem::with_default_offset(screen_width, screen_height);
+        let scale = Self::scale_factor(screen_height);
+
+        // Title
+        let title_text = "PAUSED";
+        let title_size = (screen_height * 0.069).clamp(36.0, 144.0);
+        let title_world_x = coords.center_text_x(title_text, title_size, 0.5);
+        let title_screen_pos =
+            coords.world_to_screen(vec2(title_world_x - (title_size / 2.0), -300.0 * scale));
+        gfx.text(title_text)
+            .at(title_screen_pos)
+            .size(title_size)
+            .color(COLOR_TEXT_GREEN);
+
+        // Current score so the player knows what's at stake
+        let score_text = format!("Score: {}", score_manager.score());
+        let score_size = (screen_height * 0.027).clamp(14.0, 56.0);
+        let score_world_x = coords.center_text_x(&score_text, score_size, 0.5);
+        let score_screen_pos = coords.world_to_screen(vec2(score_world_x, -200.0 * scale));
+        gfx.text(&score_text)
+            .at(score_screen_pos)
+            .size(score_size)
+            .color(COLOR_DARK_GRAY);
+
+        match self.confirming {
+            Some(action) => {
+                let question = match action {
+                    PauseAction::Restart => "Restart this game?",
+                    _ => "Quit this game?",
+                };
+                let question_size = (screen_height * 0.031).clamp(20.0, 48.0);
+                let question_world_x = coords.center_text_x(question, question_size, 0.5);
+                let question_screen_pos = coords
+                    .world_to_screen(vec2(question_world_x, Self::BASE_FIRST_BUTTON_Y * scale));
+                gfx.text(question)
+                    .at(question_screen_pos)
+                    .size(question_size)
+                    .color(COLOR_ORANGE);
+
+                self.confirm_yes_button
+                    .draw(gfx, screen_width, screen_height);
+                self.confirm_no_button
+                    .draw(gfx, screen_width, screen_height);
+            }
+            None => {
+                self.resume_button.draw(gfx, screen_width, screen_height);
+                self.restart_button.draw(gfx, screen_width, screen_height);
+                self.settings_button.draw(gfx, screen_width, screen_height);
+                self.quit_button.draw(gfx, screen_width, screen_height);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PauseAction {
+    None,
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
+
+impl Default for PauseMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,271 @@
+use crate::tetris_shape::{
+    GAMEPLAY_PIECE_COUNT, SHAPE_ELL, SHAPE_JAY, SHAPE_SLEW, SHAPE_STRAIGHT, SHAPE_TEE, SHAPE_ZED,
+};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// The single RNG that drives every gameplay decision
+/// Seeded per game so a run can be reproduced exactly from its seed
+/// ChaCha8 is a fixed algorithm, unlike `StdRng` which may change between rand releases
+pub type GameRng = rand_chacha::ChaCha8Rng;
+
+/// Name of the `GameRng` algorithm, stored in replays so they're never played back with another
+pub const RNG_ALGORITHM: &str = "chacha8";
+
+/// Create the gameplay RNG for a seed
+pub fn seeded_rng(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
+
+/// Decides which piece comes next
+/// Implementations return shape indices for `ShapeName::get_shape_by_index`
+pub trait Randomizer {
+    /// Pick the next piece to spawn, drawing randomness only from the game RNG
+    fn next_piece(&mut self, rng: &mut GameRng) -> i32;
+}
+
+/// Available piece randomizers, selectable per game
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum RandomizerKind {
+    /// Every piece is an independent roll (original Retris behavior)
+    PureRandom,
+    /// Shuffled bags of all seven pieces (modern Guideline)
+    #[default]
+    SevenBag,
+    /// Single reroll when the same piece comes up twice (NES)
+    Nes,
+    /// History of the last four pieces with several rerolls (TGM)
+    Tgm,
+}
+
+impl RandomizerKind {
+    /// Every randomizer, in the order the rules screen cycles through them
+    pub const ALL: [RandomizerKind; 4] = [
+        RandomizerKind::SevenBag,
+        RandomizerKind::PureRandom,
+        RandomizerKind::Nes,
+        RandomizerKind::Tgm,
+    ];
+
+    /// Name shown on the rules screen
+    pub fn name(self) -> &'static str {
+        match self {
+            RandomizerKind::PureRandom => "Pure Random",
+            RandomizerKind::SevenBag => "7-Bag",
+            RandomizerKind::Nes => "NES",
+            RandomizerKind::Tgm => "TGM History",
+        }
+    }
+
+    /// Create a fresh randomizer of this kind
+    pub fn create(self) -> Box<dyn Randomizer> {
+        match self {
+            RandomizerKind::PureRandom => Box::new(PureRandom),
+            RandomizerKind::SevenBag => Box::new(SevenBag::new()),
+            RandomizerKind::Nes => Box::new(NesRandomizer::new()),
+            RandomizerKind::Tgm => Box::new(TgmRandomizer::new()),
+        }
+    }
+}
+
+/// Uniformly random pieces - droughts and floods are possible
+pub struct PureRandom;
+
+impl Randomizer for PureRandom {
+    fn next_piece(&mut self, rng: &mut GameRng) -> i32 {
+        rng.random_range(0..GAMEPLAY_PIECE_COUNT)
+    }
+}
+
+/// Deals all seven pieces in a random order, then refills the bag
+/// Guarantees at most 12 pieces between two of the same kind
+pub struct SevenBag {
+    bag: Vec<i32>,
+}
+
+impl SevenBag {
+    pub fn new() -> Self {
+        Self { bag: Vec::new() }
+    }
+
+    fn refill(&mut self, rng: &mut GameRng) {
+        self.bag = (0..GAMEPLAY_PIECE_COUNT).collect();
+        self.bag.shuffle(rng);
+    }
+}
+
+impl Randomizer for SevenBag {
+    fn next_piece(&mut self, rng: &mut GameRng) -> i32 {
+        if self.bag.is_empty() {
+            self.refill(rng);
+        }
+        // Bag was just refilled, so there is always a piece to pop
+        self.bag.pop().unwrap_or(SHAPE_TEE)
+    }
+}
+
+/// NES-style randomizer
+/// Rolls an 8-sided die; a roll of 8 or a repeat of the previous piece gets one reroll
+pub struct NesRandomizer {
+    last_piece: Option<i32>,
+}
+
+impl NesRandomizer {
+    pub fn new() -> Self {
+        Self { last_piece: None }
+    }
+}
+
+impl Randomizer for NesRandomizer {
+    fn next_piece(&mut self, rng: &mut GameRng) -> i32 {
+        let roll = rng.random_range(0..GAMEPLAY_PIECE_COUNT + 1);
+        let piece = if roll == GAMEPLAY_PIECE_COUNT || Some(roll) == self.last_piece {
+            // Reroll once and accept whatever comes up
+            rng.random_range(0..GAMEPLAY_PIECE_COUNT)
+        } else {
+            roll
+        };
+        self.last_piece = Some(piece);
+        piece
+    }
+}
+
+/// TGM-style randomizer
+/// Remembers the last four pieces and rerolls up to six times to avoid repeating them
+pub struct TgmRandomizer {
+    history: [i32; 4],
+    first_piece: bool,
+}
+
+impl TgmRandomizer {
+    /// Number of rolls before accepting a piece that is still in the history
+    const MAX_ROLLS: usize = 6;
+
+    pub fn new() -> Self {
+        Self {
+            // History starts filled with snakes so they are unlikely early on
+            history: [SHAPE_ZED, SHAPE_SLEW, SHAPE_ZED, SHAPE_SLEW],
+            first_piece: true,
+        }
+    }
+}
+
+impl Randomizer for TgmRandomizer {
+    fn next_piece(&mut self, rng: &mut GameRng) -> i32 {
+        let piece = if self.first_piece {
+            // First piece is never a snake or a square, so the opening is always fair
+            self.first_piece = false;
+            const FIRST_PIECES: [i32; 4] = [SHAPE_STRAIGHT, SHAPE_TEE, SHAPE_ELL, SHAPE_JAY];
+            FIRST_PIECES[rng.random_range(0..FIRST_PIECES.len())]
+        } else {
+            let mut piece = rng.random_range(0..GAMEPLAY_PIECE_COUNT);
+            for _ in 1..Self::MAX_ROLLS {
+                if !self.history.contains(&piece) {
+                    break;
+                }
+                piece = rng.random_range(0..GAMEPLAY_PIECE_COUNT);
+            }
+            piece
+        };
+
+        // Shift the history and remember the new piece
+        self.history.rotate_right(1);
+        self.history[0] = piece;
+        piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetris_shape::SHAPE_SQUARE;
+
+    fn deal(randomizer: &mut dyn Randomizer, seed: u64, count: usize) -> Vec<i32> {
+        let mut rng = seeded_rng(seed);
+        (0..count)
+            .map(|_| randomizer.next_piece(&mut rng))
+            .collect()
+    }
+
+    /// How many pieces were the same as the one right before them
+    fn back_to_back_repeats(pieces: &[i32]) -> usize {
+        pieces.windows(2).filter(|pair| pair[0] == pair[1]).count()
+    }
+
+    #[test]
+    fn every_randomizer_deals_gameplay_pieces_reproducibly() {
+        for kind in RandomizerKind::ALL {
+            let pieces = deal(kind.create().as_mut(), 42, 1000);
+            assert!(
+                pieces
+                    .iter()
+                    .all(|piece| (0..GAMEPLAY_PIECE_COUNT).contains(piece)),
+                "{}",
+                kind.name()
+            );
+            assert_eq!(
+                pieces,
+                deal(kind.create().as_mut(), 42, 1000),
+                "{}",
+                kind.name()
+            );
+        }
+    }
+
+    #[test]
+    fn seven_bag_deals_each_piece_once_per_bag() {
+        let pieces = deal(&mut SevenBag::new(), 7, 7 * 100);
+        for bag in pieces.chunks(7) {
+            let mut bag = bag.to_vec();
+            bag.sort_unstable();
+            assert_eq!(bag, (0..GAMEPLAY_PIECE_COUNT).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn nes_rerolls_make_repeats_rarer_than_pure_random() {
+        // Pure random repeats about 1 piece in 7, the NES reroll about 1 in 28
+        let count = 10_000;
+        let pure_repeats = back_to_back_repeats(&deal(&mut PureRandom, 3, count));
+        let nes_repeats = back_to_back_repeats(&deal(&mut NesRandomizer::new(), 3, count));
+        assert!(
+            pure_repeats > count / 10,
+            "pure random repeated {pure_repeats} times"
+        );
+        assert!(nes_repeats < count / 20, "NES repeated {nes_repeats} times");
+    }
+
+    #[test]
+    fn tgm_opens_without_a_snake_or_square() {
+        for seed in 0..500 {
+            let first = deal(&mut TgmRandomizer::new(), seed, 1)[0];
+            assert!(
+                ![SHAPE_SLEW, SHAPE_ZED, SHAPE_SQUARE].contains(&first),
+                "seed {seed} opened with {first}"
+            );
+        }
+    }
+
+    #[test]
+    fn tgm_remembers_the_last_four_pieces() {
+        let mut randomizer = TgmRandomizer::new();
+        let pieces = deal(&mut randomizer, 11, 50);
+        let mut last_four: Vec<i32> = pieces[pieces.len() - 4..].to_vec();
+        last_four.reverse();
+        assert_eq!(randomizer.history.to_vec(), last_four);
+    }
+
+    #[test]
+    fn tgm_rarely_deals_a_piece_from_its_history() {
+        let pieces = deal(&mut TgmRandomizer::new(), 5, 10_000);
+        let from_history = pieces
+            .windows(5)
+            .filter(|window| window[..4].contains(&window[4]))
+            .count();
+        assert!(
+            from_history < pieces.len() / 20,
+            "{from_history} pieces repeated the history"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::engine::{Engine, GameEvent, TickInput};
+use crate::handling::HandlingProfile;
+use crate::randomizer::RNG_ALGORITHM;
+use crate::ruleset::Ruleset;
+use serde::{Deserialize, Serialize};
+
+/// Current replay file format, bumped whenever old replays would play back differently
+pub const REPLAY_VERSION: u32 = 2;
+
+/// Playback speeds the replay player steps through (1.0 is real time)
+const PLAYBACK_SPEEDS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+
+/// Index of real-time speed in `PLAYBACK_SPEEDS`
+const NORMAL_SPEED_INDEX: usize = 2;
+
+/// Something that changed on a tick, in the order it happened
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayEntry {
+    /// The input every tick gets from here on, until the next input entry
+    Input { tick: u64, buttons: u8 },
+    /// Handling was changed mid-game from the settings screen
+    Handling {
+        tick: u64,
+        handling: HandlingProfile,
+    },
+}
+
+impl ReplayEntry {
+    fn tick(&self) -> u64 {
+        match *self {
+            ReplayEntry::Input { tick, .. } | ReplayEntry::Handling { tick, .. } => tick,
+        }
+    }
+}
+
+/// A whole game: everything the engine needs to play it out again exactly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    /// Algorithm the seed was fed to (`RNG_ALGORITHM` when recorded)
+    #[serde(default)]
+    pub rng: String,
+    pub seed: u64,
+    pub ruleset: Ruleset,
+    pub handling: HandlingProfile,
+    pub entries: Vec<ReplayEntry>,
+    /// Ticks the game lasted
+    pub length_ticks: u64,
+    /// Score at the end (to show in a replay list without playing it)
+    pub final_score: u64,
+}
+
+/// Pack a tick input into one byte, one bit per input (bit order is part of the file format)
+fn input_to_buttons(input: &TickInput) -> u8 {
+    [
+        input.left,
+        input.right,
+        input.soft_drop,
+        input.hard_drop,
+        input.rotate_cw,
+        input.rotate_ccw,
+        input.rotate_180,
+        input.hold,
+    ]
+    .iter()
+    .enumerate()
+    .fold(0, |buttons, (bit, &down)| buttons | ((down as u8) << bit))
+}
+
+/// Unpack a byte written by `input_to_buttons`
+fn buttons_to_input(buttons: u8) -> TickInput {
+    let bit = |index: u8| buttons & (1 << index) != 0;
+    TickInput {
+        left: bit(0),
+        right: bit(1),
+        soft_drop: bit(2),
+        hard_drop: bit(3),
+        rotate_cw: bit(4),
+        rotate_ccw: bit(5),
+        rotate_180: bit(6),
+        hold: bit(7),
+    }
+}
+
+/// Writes down every tick of a live game
+pub struct ReplayRecorder {
+    replay: Replay,
+    last_buttons: Option<u8>, // Input of the previous tick, only changes are logged
+}
+
+impl ReplayRecorder {
+    pub fn new(seed: u64, ruleset: Ruleset, handling: HandlingProfile) -> Self {
+        Self {
+            replay: Replay {
+                version: REPLAY_VERSION,
+                rng: RNG_ALGORITHM.to_string(),
+                seed,
+                ruleset,
+                handling,
+                entries: Vec::new(),
+                length_ticks: 0,
+                final_score: 0,
+            },
+            last_buttons: None,
+        }
+    }
+
+    /// Run one engine tick with the player's input, logging the input if it changed
+    pub fn tick(&mut self, engine: &mut Engine, input: &TickInput) -> Vec<GameEvent> {
+        let buttons = input_to_buttons(input);
+        if self.last_buttons != Some(buttons) {
+            self.replay.entries.push(ReplayEntry::Input {
+                tick: engine.ticks(),
+                buttons,
+            });
+            self.last_buttons = Some(buttons);
+        }
+        engine.tick(input)
+    }
+
+    /// Log a handling change so playback switches at the same tick
+    pub fn record_handling(&mut self, tick: u64, handling: HandlingProfile) {
+        self.replay
+            .entries
+            .push(ReplayEntry::Handling { tick, handling });
+    }
+
+    /// The finished replay, stamped with how the game ended
+    pub fn finish(&self, engine: &Engine) -> Replay {
+        Replay {
+            length_ticks: engine.ticks(),
+            final_score: engine.score_manager().score(),
+            ..self.replay.clone()
+        }
+    }
+}
+
+/// Feeds a recorded game back into the engine, with pause and speed control
+pub struct ReplayPlayer {
+    replay: Replay,
+    next_entry: usize, // First entry not applied yet
+    input: TickInput,  // Input from the latest applied entry
+    speed_index: usize,
+    paused: bool,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_entry: 0,
+            input: TickInput::default(),
+            speed_index: NORMAL_SPEED_INDEX,
+            paused: false,
+        }
+    }
+
+    /// Run the next recorded tick through the engine
+    pub fn tick(&mut self, engine: &mut Engine) -> Vec<GameEvent> {
+        let tick = engine.ticks();
+        while let Some(entry) = self
+            .replay
+            .entries
+            .get(self.next_entry)
+            .filter(|entry| entry.tick() <= tick)
+        {
+            match *entry {
+                ReplayEntry::Input { buttons, .. } => self.input = buttons_to_input(buttons),
+                ReplayEntry::Handling { handling, .. } => engine.set_handling(handling),
+            }
+            self.next_entry += 1;
+        }
+        engine.tick(&self.input)
+    }
+
+    /// True once every recorded tick has been played
+    pub fn is_finished(&self, engine: &Engine) -> bool {
+        engine.is_game_over() || engine.ticks() >= self.replay.length_ticks
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn speed_up(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(PLAYBACK_SPEEDS.len() - 1);
+    }
+
+    pub fn slow_down(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    /// Playback speed as a multiple of real time
+    pub fn speed(&self) -> f32 {
+        PLAYBACK_SPEEDS[self.speed_index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 20240611;
+
+    #[test]
+    fn every_button_combination_packs_into_a_byte_and_back() {
+        for buttons in 0..=u8::MAX {
+            assert_eq!(input_to_buttons(&buttons_to_input(buttons)), buttons);
+        }
+    }
+
+    /// Scripted play for the replay test: shift, turn, sometimes hold, then hard drop
+    fn scripted_input(tick: u64) -> TickInput {
+        let piece_number = tick / 20;
+        let step = tick % 20;
+        TickInput {
+            left: piece_number.is_multiple_of(3) && step < 4,
+            right: piece_number % 3 == 1 && step < 4 + piece_number % 5,
+            soft_drop: step == 12,
+            hard_drop: step == 19,
+            rotate_cw: step == 6 && piece_number.is_multiple_of(2),
+            rotate_ccw: step == 8 && piece_number % 5 == 1,
+            rotate_180: step == 10 && piece_number % 7 == 3,
+            hold: step == 14 && piece_number % 4 == 2,
+        }
+    }
+
+    fn sorted_cells(engine: &Engine) -> Vec<(i32, i32, i32)> {
+        let mut cells: Vec<_> = engine.board().cells().collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn seeded_replay_plays_back_the_same_game() {
+        let ruleset = Ruleset::default();
+        let handling = HandlingProfile::default();
+        let mut engine = Engine::new(&ruleset, handling, Some(SEED));
+        let mut recorder = ReplayRecorder::new(SEED, ruleset.clone(), handling);
+        let mut recorded_events = Vec::new();
+        while engine.ticks() < 3000 && !engine.is_game_over() {
+            let input = scripted_input(engine.ticks());
+            recorded_events.extend(recorder.tick(&mut engine, &input));
+        }
+        let replay = recorder.finish(&engine);
+
+        let mut playback = Engine::new(&replay.ruleset, replay.handling, Some(replay.seed));
+        let mut player = ReplayPlayer::new(replay.clone());
+        let mut played_events = Vec::new();
+        while !player.is_finished(&playback) {
+            played_events.extend(player.tick(&mut playback));
+        }
+
+        assert!(!recorded_events.is_empty());
+        assert_eq!(played_events, recorded_events);
+        assert_eq!(playback.ticks(), engine.ticks());
+        assert_eq!(playback.score_manager().score(), replay.final_score);
+        assert_eq!(sorted_cells(&playback), sorted_cells(&engine));
+    }
+}
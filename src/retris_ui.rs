@@ -445,6 +445,11 @@ impl Button {
         self.height = height;
     }
 
+    /// Change the button's label (for toggle buttons)
+    pub fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+    }
+
     /// Update button position based on actual screen dimensions
     /// Currently buttons are positioned in world coordinates at creation, so this is a no-op
     /// but included for consistency with other UI elements
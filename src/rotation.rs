@@ -0,0 +1,505 @@
+use crate::tetris_shape::{GAMEPLAY_PIECE_COUNT, SHAPE_SQUARE, SHAPE_STRAIGHT};
+use serde::{Deserialize, Serialize};
+
+/// Number of orientations a piece can be in: spawn (0), right (1), 180 (2) and left (3)
+pub const ORIENTATION_COUNT: usize = 4;
+
+/// The four cells of a piece, as (x, y) with y pointing down
+pub type PieceCells = [(i32, i32); 4];
+
+/// Offsets tried in order when a rotation collides, as (x, y) with y pointing down
+pub type KickTable = &'static [(i32, i32)];
+
+/// Cell of the bounding box the piece rotates around (the piece's grid position)
+/// Tables below are written in bounding-box coordinates so they read like the usual diagrams
+const BOX_PIVOT: (i32, i32) = (1, 1);
+
+/// Defines how pieces are laid out in each orientation and how rotations get kicked
+/// Shape indices match `ShapeName::get_shape_by_index`
+pub trait RotationSystem {
+    /// Cells of a piece in an orientation, relative to the piece's grid position
+    fn cells(&self, shape_index: i32, orientation: usize) -> PieceCells;
+
+    /// Offsets to try, in order, when rotating a piece from one orientation to another
+    /// The first offset that fits wins; an empty or exhausted table means the rotation fails
+    fn kicks(&self, shape_index: i32, from: usize, to: usize) -> KickTable;
+
+    /// True if a T-spin made with this kick always counts as a full T-spin, never a mini
+    fn is_full_t_spin_kick(
+        &self,
+        _shape_index: i32,
+        _from: usize,
+        _to: usize,
+        _kick_index: usize,
+    ) -> bool {
+        false
+    }
+}
+
+/// Available rotation systems, selectable per game
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum RotationSystemKind {
+    /// Super Rotation System (modern Guideline) with floor kicks
+    #[default]
+    Srs,
+    /// Arika Rotation System (TGM) - bottom-aligned pieces, simple left/right kicks
+    Ars,
+    /// Nintendo Rotation System (NES) - no kicks at all
+    Nrs,
+}
+
+impl RotationSystemKind {
+    /// Every rotation system, in the order the rules screen cycles through them
+    pub const ALL: [RotationSystemKind; 3] = [
+        RotationSystemKind::Srs,
+        RotationSystemKind::Ars,
+        RotationSystemKind::Nrs,
+    ];
+
+    /// Name shown on the rules screen
+    pub fn name(self) -> &'static str {
+        match self {
+            RotationSystemKind::Srs => "SRS",
+            RotationSystemKind::Ars => "ARS",
+            RotationSystemKind::Nrs => "NRS",
+        }
+    }
+
+    /// Create the rotation system of this kind
+    pub fn create(self) -> Box<dyn RotationSystem> {
+        match self {
+            RotationSystemKind::Srs => Box::new(Srs),
+            RotationSystemKind::Ars => Box::new(Ars),
+            RotationSystemKind::Nrs => Box::new(Nrs),
+        }
+    }
+}
+
+/// Look up a piece state in a table and move it from bounding-box to pivot coordinates
+fn cells_from_table(
+    table: &[[PieceCells; ORIENTATION_COUNT]; GAMEPLAY_PIECE_COUNT as usize],
+    shape_index: i32,
+    orientation: usize,
+) -> PieceCells {
+    let shape = shape_index.clamp(0, GAMEPLAY_PIECE_COUNT - 1) as usize;
+    table[shape][orientation % ORIENTATION_COUNT].map(|(x, y)| (x - BOX_PIVOT.0, y - BOX_PIVOT.1))
+}
+
+/// True if going from `from` to `to` is a single clockwise turn
+fn is_clockwise(from: usize, to: usize) -> bool {
+    (from + 1) % ORIENTATION_COUNT == to % ORIENTATION_COUNT
+}
+
+/// True if going from `from` to `to` is a single counter-clockwise turn
+fn is_counter_clockwise(from: usize, to: usize) -> bool {
+    (from + ORIENTATION_COUNT - 1) % ORIENTATION_COUNT == to % ORIENTATION_COUNT
+}
+
+/// Only try the rotation in place
+const NO_KICKS: KickTable = &[(0, 0)];
+
+// ============================================================================
+// SRS (Guideline)
+// ============================================================================
+
+/// Super Rotation System: true rotation inside the bounding box plus five kick tests
+pub struct Srs;
+
+/// Piece states in bounding-box coordinates, indexed by shape then orientation
+const SRS_STATES: [[PieceCells; ORIENTATION_COUNT]; GAMEPLAY_PIECE_COUNT as usize] = [
+    // Straight (I)
+    [
+        [(0, 1), (1, 1), (2, 1), (3, 1)],
+        [(2, 0), (2, 1), (2, 2), (2, 3)],
+        [(0, 2), (1, 2), (2, 2), (3, 2)],
+        [(1, 0), (1, 1), (1, 2), (1, 3)],
+    ],
+    // Square (O)
+    [
+        [(1, 0), (2, 0), (1, 1), (2, 1)],
+        [(1, 0), (2, 0), (1, 1), (2, 1)],
+        [(1, 0), (2, 0), (1, 1), (2, 1)],
+        [(1, 0), (2, 0), (1, 1), (2, 1)],
+    ],
+    // Tee (T)
+    [
+        [(1, 0), (0, 1), (1, 1), (2, 1)],
+        [(1, 0), (1, 1), (2, 1), (1, 2)],
+        [(0, 1), (1, 1), (2, 1), (1, 2)],
+        [(1, 0), (0, 1), (1, 1), (1, 2)],
+    ],
+    // Ell (L)
+    [
+        [(2, 0), (0, 1), (1, 1), (2, 1)],
+        [(1, 0), (1, 1), (1, 2), (2, 2)],
+        [(0, 1), (1, 1), (2, 1), (0, 2)],
+        [(0, 0), (1, 0), (1, 1), (1, 2)],
+    ],
+    // Jay (J)
+    [
+        [(0, 0), (0, 1), (1, 1), (2, 1)],
+        [(1, 0), (2, 0), (1, 1), (1, 2)],
+        [(0, 1), (1, 1), (2, 1), (2, 2)],
+        [(1, 0), (1, 1), (0, 2), (1, 2)],
+    ],
+    // Slew (S)
+    [
+        [(1, 0), (2, 0), (0, 1), (1, 1)],
+        [(1, 0), (1, 1), (2, 1), (2, 2)],
+        [(1, 1), (2, 1), (0, 2), (1, 2)],
+        [(0, 0), (0, 1), (1, 1), (1, 2)],
+    ],
+    // Zed (Z)
+    [
+        [(0, 0), (1, 0), (1, 1), (2, 1)],
+        [(2, 0), (1, 1), (2, 1), (1, 2)],
+        [(0, 1), (1, 1), (1, 2), (2, 2)],
+        [(1, 0), (0, 1), (1, 1), (0, 2)],
+    ],
+];
+
+/// J, L, S, T, Z kicks for clockwise turns, indexed by the starting orientation
+const SRS_JLSTZ_CW_KICKS: [[(i32, i32); 5]; ORIENTATION_COUNT] = [
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],  // 0 -> R
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],    // R -> 2
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],     // 2 -> L
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)], // L -> 0
+];
+
+/// J, L, S, T, Z kicks for counter-clockwise turns, indexed by the starting orientation
+const SRS_JLSTZ_CCW_KICKS: [[(i32, i32); 5]; ORIENTATION_COUNT] = [
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],     // 0 -> L
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],    // R -> 0
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],  // 2 -> R
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)], // L -> 2
+];
+
+/// I kicks for clockwise turns, indexed by the starting orientation
+const SRS_I_CW_KICKS: [[(i32, i32); 5]; ORIENTATION_COUNT] = [
+    [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)], // 0 -> R
+    [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)], // R -> 2
+    [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)], // 2 -> L
+    [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)], // L -> 0
+];
+
+/// I kicks for counter-clockwise turns, indexed by the starting orientation
+const SRS_I_CCW_KICKS: [[(i32, i32); 5]; ORIENTATION_COUNT] = [
+    [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)], // 0 -> L
+    [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)], // R -> 0
+    [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)], // 2 -> R
+    [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)], // L -> 2
+];
+
+/// 180 degree kicks (SRS defines none, these follow the common SRS+ extension)
+/// Indexed by the starting orientation; the O piece still never kicks
+const SRS_180_KICKS: [[(i32, i32); 6]; ORIENTATION_COUNT] = [
+    [(0, 0), (0, -1), (1, -1), (-1, -1), (1, 0), (-1, 0)], // 0 -> 2
+    [(0, 0), (1, 0), (1, -2), (1, -1), (0, -2), (0, -1)],  // R -> L
+    [(0, 0), (0, 1), (-1, 1), (1, 1), (-1, 0), (1, 0)],    // 2 -> 0
+    [(0, 0), (-1, 0), (-1, -2), (-1, -1), (0, -2), (0, -1)], // L -> R
+];
+
+impl RotationSystem for Srs {
+    fn cells(&self, shape_index: i32, orientation: usize) -> PieceCells {
+        cells_from_table(&SRS_STATES, shape_index, orientation)
+    }
+
+    fn kicks(&self, shape_index: i32, from: usize, to: usize) -> KickTable {
+        let from = from % ORIENTATION_COUNT;
+        let (cw_kicks, ccw_kicks) = match shape_index {
+            SHAPE_SQUARE => return NO_KICKS,
+            SHAPE_STRAIGHT => (&SRS_I_CW_KICKS, &SRS_I_CCW_KICKS),
+            _ => (&SRS_JLSTZ_CW_KICKS, &SRS_JLSTZ_CCW_KICKS),
+        };
+        if is_clockwise(from, to) {
+            &cw_kicks[from]
+        } else if is_counter_clockwise(from, to) {
+            &ccw_kicks[from]
+        } else if from != to % ORIENTATION_COUNT {
+            &SRS_180_KICKS[from]
+        } else {
+            NO_KICKS
+        }
+    }
+
+    /// The last test of the JLSTZ table on a quarter turn (the "TST" kick) is always full
+    /// 180 degree kicks come from a different table and never promote a mini
+    fn is_full_t_spin_kick(
+        &self,
+        shape_index: i32,
+        from: usize,
+        to: usize,
+        kick_index: usize,
+    ) -> bool {
+        let quarter_turn = is_clockwise(from, to) || is_counter_clockwise(from, to);
+        let uses_jlstz_table = shape_index != SHAPE_SQUARE && shape_index != SHAPE_STRAIGHT;
+        quarter_turn && uses_jlstz_table && kick_index == SRS_JLSTZ_CW_KICKS[0].len() - 1
+    }
+}
+
+// ============================================================================
+// ARS (TGM)
+// ============================================================================
+
+/// Arika Rotation System: pieces hug the bottom of their box, I/S/Z have two states
+/// A blocked rotation tries one cell right, then one cell left (the I piece never kicks)
+/// The same kicks apply in both directions and for 180 degree turns
+pub struct Ars;
+
+/// Piece states in bounding-box coordinates, indexed by shape then orientation
+const ARS_STATES: [[PieceCells; ORIENTATION_COUNT]; GAMEPLAY_PIECE_COUNT as usize] = [
+    // Straight (I)
+    [
+        [(0, 1), (1, 1), (2, 1), (3, 1)],
+        [(2, 0), (2, 1), (2, 2), (2, 3)],
+        [(0, 1), (1, 1), (2, 1), (3, 1)],
+        [(2, 0), (2, 1), (2, 2), (2, 3)],
+    ],
+    // Square (O)
+    [
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+    ],
+    // Tee (T)
+    [
+        [(0, 1), (1, 1), (2, 1), (1, 2)],
+        [(1, 0), (0, 1), (1, 1), (1, 2)],
+        [(1, 1), (0, 2), (1, 2), (2, 2)],
+        [(1, 0), (1, 1), (2, 1), (1, 2)],
+    ],
+    // Ell (L)
+    [
+        [(0, 1), (1, 1), (2, 1), (0, 2)],
+        [(0, 0), (1, 0), (1, 1), (1, 2)],
+        [(2, 1), (0, 2), (1, 2), (2, 2)],
+        [(1, 0), (1, 1), (1, 2), (2, 2)],
+    ],
+    // Jay (J)
+    [
+        [(0, 1), (1, 1), (2, 1), (2, 2)],
+        [(1, 0), (1, 1), (0, 2), (1, 2)],
+        [(0, 1), (0, 2), (1, 2), (2, 2)],
+        [(1, 0), (2, 0), (1, 1), (1, 2)],
+    ],
+    // Slew (S)
+    [
+        [(1, 1), (2, 1), (0, 2), (1, 2)],
+        [(0, 0), (0, 1), (1, 1), (1, 2)],
+        [(1, 1), (2, 1), (0, 2), (1, 2)],
+        [(0, 0), (0, 1), (1, 1), (1, 2)],
+    ],
+    // Zed (Z)
+    [
+        [(0, 1), (1, 1), (1, 2), (2, 2)],
+        [(2, 0), (1, 1), (2, 1), (1, 2)],
+        [(0, 1), (1, 1), (1, 2), (2, 2)],
+        [(2, 0), (1, 1), (2, 1), (1, 2)],
+    ],
+];
+
+/// Kicks for every piece except I: in place, then right, then left
+const ARS_KICKS: KickTable = &[(0, 0), (1, 0), (-1, 0)];
+
+impl RotationSystem for Ars {
+    fn cells(&self, shape_index: i32, orientation: usize) -> PieceCells {
+        cells_from_table(&ARS_STATES, shape_index, orientation)
+    }
+
+    fn kicks(&self, shape_index: i32, _from: usize, _to: usize) -> KickTable {
+        match shape_index {
+            SHAPE_STRAIGHT | SHAPE_SQUARE => NO_KICKS,
+            _ => ARS_KICKS,
+        }
+    }
+}
+
+// ============================================================================
+// NRS (NES)
+// ============================================================================
+
+/// Nintendo Rotation System: rotations (including 180) either fit in place or fail
+pub struct Nrs;
+
+/// Piece states in bounding-box coordinates, indexed by shape then orientation
+const NRS_STATES: [[PieceCells; ORIENTATION_COUNT]; GAMEPLAY_PIECE_COUNT as usize] = [
+    // Straight (I)
+    [
+        [(0, 1), (1, 1), (2, 1), (3, 1)],
+        [(2, 0), (2, 1), (2, 2), (2, 3)],
+        [(0, 1), (1, 1), (2, 1), (3, 1)],
+        [(2, 0), (2, 1), (2, 2), (2, 3)],
+    ],
+    // Square (O)
+    [
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+    ],
+    // Tee (T)
+    [
+        [(0, 1), (1, 1), (2, 1), (1, 2)],
+        [(1, 0), (0, 1), (1, 1), (1, 2)],
+        [(1, 0), (0, 1), (1, 1), (2, 1)],
+        [(1, 0), (1, 1), (2, 1), (1, 2)],
+    ],
+    // Ell (L)
+    [
+        [(0, 1), (1, 1), (2, 1), (0, 2)],
+        [(0, 0), (1, 0), (1, 1), (1, 2)],
+        [(2, 0), (0, 1), (1, 1), (2, 1)],
+        [(1, 0), (1, 1), (1, 2), (2, 2)],
+    ],
+    // Jay (J)
+    [
+        [(0, 1), (1, 1), (2, 1), (2, 2)],
+        [(1, 0), (1, 1), (0, 2), (1, 2)],
+        [(0, 0), (0, 1), (1, 1), (2, 1)],
+        [(1, 0), (2, 0), (1, 1), (1, 2)],
+    ],
+    // Slew (S)
+    [
+        [(1, 1), (2, 1), (0, 2), (1, 2)],
+        [(1, 0), (1, 1), (2, 1), (2, 2)],
+        [(1, 1), (2, 1), (0, 2), (1, 2)],
+        [(1, 0), (1, 1), (2, 1), (2, 2)],
+    ],
+    // Zed (Z)
+    [
+        [(0, 1), (1, 1), (1, 2), (2, 2)],
+        [(2, 0), (1, 1), (2, 1), (1, 2)],
+        [(0, 1), (1, 1), (1, 2), (2, 2)],
+        [(2, 0), (1, 1), (2, 1), (1, 2)],
+    ],
+];
+
+impl RotationSystem for Nrs {
+    fn cells(&self, shape_index: i32, orientation: usize) -> PieceCells {
+        cells_from_table(&NRS_STATES, shape_index, orientation)
+    }
+
+    fn kicks(&self, _shape_index: i32, _from: usize, _to: usize) -> KickTable {
+        NO_KICKS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetris_shape::{SHAPE_TEE, SHAPE_ZED};
+
+    const ALL_TURNS: [(usize, usize); 12] = [
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (0, 3),
+        (1, 0),
+        (2, 1),
+        (3, 2),
+        (0, 2),
+        (1, 3),
+        (2, 0),
+        (3, 1),
+    ];
+
+    #[test]
+    fn every_piece_state_has_four_different_cells() {
+        for kind in RotationSystemKind::ALL {
+            let system = kind.create();
+            for shape_index in 0..GAMEPLAY_PIECE_COUNT {
+                for orientation in 0..ORIENTATION_COUNT {
+                    let cells = system.cells(shape_index, orientation);
+                    for (index, cell) in cells.iter().enumerate() {
+                        assert!(
+                            !cells[index + 1..].contains(cell),
+                            "{} shape {shape_index} orientation {orientation}",
+                            kind.name()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_kick_table_tries_in_place_first() {
+        for kind in RotationSystemKind::ALL {
+            let system = kind.create();
+            for shape_index in 0..GAMEPLAY_PIECE_COUNT {
+                for (from, to) in ALL_TURNS {
+                    assert_eq!(system.kicks(shape_index, from, to)[0], (0, 0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn srs_quarter_turn_kicks_undo_each_other() {
+        // Turning back the other way tries the same offsets mirrored, as in the SRS tables
+        for shape_index in [SHAPE_STRAIGHT, SHAPE_TEE, SHAPE_ZED] {
+            for (from, to) in &ALL_TURNS[..8] {
+                let there = Srs.kicks(shape_index, *from, *to);
+                let back = Srs.kicks(shape_index, *to, *from);
+                assert_eq!(there.len(), 5);
+                for (&(x, y), &(back_x, back_y)) in there.iter().zip(back) {
+                    assert_eq!(
+                        (x, y),
+                        (-back_x, -back_y),
+                        "shape {shape_index} {from} -> {to}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn srs_uses_its_own_tables_for_the_straight_piece_and_180_turns() {
+        assert_eq!(Srs.kicks(SHAPE_STRAIGHT, 0, 1), &SRS_I_CW_KICKS[0]);
+        assert_eq!(Srs.kicks(SHAPE_STRAIGHT, 0, 3), &SRS_I_CCW_KICKS[0]);
+        assert_eq!(Srs.kicks(SHAPE_TEE, 0, 1), &SRS_JLSTZ_CW_KICKS[0]);
+        assert_eq!(Srs.kicks(SHAPE_TEE, 2, 0), &SRS_180_KICKS[2]);
+        assert_eq!(Srs.kicks(SHAPE_STRAIGHT, 1, 3), &SRS_180_KICKS[1]);
+        assert_eq!(Srs.kicks(SHAPE_SQUARE, 0, 2), NO_KICKS);
+        assert_eq!(Srs.kicks(SHAPE_TEE, 1, 1), NO_KICKS);
+    }
+
+    #[test]
+    fn only_the_last_srs_quarter_turn_kick_forces_a_full_t_spin() {
+        for (from, to) in &ALL_TURNS[..8] {
+            for kick_index in 0..4 {
+                assert!(!Srs.is_full_t_spin_kick(SHAPE_TEE, *from, *to, kick_index));
+            }
+            assert!(Srs.is_full_t_spin_kick(SHAPE_TEE, *from, *to, 4));
+            assert!(!Srs.is_full_t_spin_kick(SHAPE_STRAIGHT, *from, *to, 4));
+        }
+        for (from, to) in &ALL_TURNS[8..] {
+            for kick_index in 0..SRS_180_KICKS[0].len() {
+                assert!(!Srs.is_full_t_spin_kick(SHAPE_TEE, *from, *to, kick_index));
+            }
+        }
+        assert!(!Ars.is_full_t_spin_kick(SHAPE_TEE, 0, 1, 2));
+        assert!(!Nrs.is_full_t_spin_kick(SHAPE_TEE, 0, 1, 0));
+    }
+
+    #[test]
+    fn ars_tries_right_then_left_except_for_the_straight_and_square() {
+        for (from, to) in ALL_TURNS {
+            assert_eq!(Ars.kicks(SHAPE_TEE, from, to), ARS_KICKS);
+            assert_eq!(Ars.kicks(SHAPE_ZED, from, to), &[(0, 0), (1, 0), (-1, 0)]);
+            assert_eq!(Ars.kicks(SHAPE_STRAIGHT, from, to), NO_KICKS);
+            assert_eq!(Ars.kicks(SHAPE_SQUARE, from, to), NO_KICKS);
+        }
+    }
+
+    #[test]
+    fn nrs_never_kicks() {
+        for shape_index in 0..GAMEPLAY_PIECE_COUNT {
+            for (from, to) in ALL_TURNS {
+                assert_eq!(Nrs.kicks(shape_index, from, to), NO_KICKS);
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::gravity::GravityKind;
+use crate::randomizer::RandomizerKind;
+use crate::rotation::RotationSystemKind;
+use crate::ruleset::LevelUpEffect;
+use crate::scoring::ScoringRuleKind;
+use serde::{Deserialize, Serialize};
+
+/// Rules picked on the rules screen, used by every mode that doesn't fix them itself
+/// Saved through `Storage`; fields missing from an older save keep their defaults
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleOptions {
+    /// How the next piece is chosen
+    pub randomizer: RandomizerKind,
+    /// How pieces turn and kick off walls
+    pub rotation_system: RotationSystemKind,
+    /// How clears are turned into points (each rule keeps its own high score)
+    pub scoring_rule: ScoringRuleKind,
+    /// Fall speed curve across levels (Ultra keeps its fixed speed)
+    pub gravity: GravityKind,
+    /// Whether leveling up in Marathon wipes the board or only flashes it
+    /// The other modes always flash, a wipe would clear their stack for free
+    pub marathon_level_up: LevelUpEffect,
+    /// Chance (in percent) of a dig garbage row's hole moving away from the one below it
+    pub dig_messiness: u8,
+    /// Seconds between new dig garbage rows rising from the bottom (0 adds none)
+    pub dig_rise_seconds: u8,
+}
+
+impl Default for RuleOptions {
+    fn default() -> Self {
+        Self {
+            randomizer: RandomizerKind::default(),
+            rotation_system: RotationSystemKind::default(),
+            scoring_rule: ScoringRuleKind::default(),
+            gravity: GravityKind::default(),
+            marathon_level_up: LevelUpEffect::default(),
+            dig_messiness: 30,
+            dig_rise_seconds: 0,
+        }
+    }
+}
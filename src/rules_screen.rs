@@ -0,0 +1,292 @@
+use crate::coordinate_system::CoordinateSystem;
+use crate::gravity::GravityKind;
+use crate::randomizer::RandomizerKind;
+use crate::retris_colors::*;
+use crate::retris_ui::Button;
+use crate::rotation::RotationSystemKind;
+use crate::rule_options::RuleOptions;
+use crate::ruleset::LevelUpEffect;
+use crate::scoring::ScoringRuleKind;
+use crate::storage::Storage;
+use egor::input::{Input, KeyCode};
+use egor::math::vec2;
+use egor::render::Graphics;
+
+/// Step through a list of choices, wrapping around at either end
+fn cycle<T: Copy + PartialEq>(choices: &[T], current: T, steps: i32) -> T {
+    let index = choices
+        .iter()
+        .position(|&choice| choice == current)
+        .unwrap_or(0);
+    let len = choices.len() as i32;
+    choices[(index as i32 + steps).rem_euclid(len) as usize]
+}
+
+/// Dig messiness choices, in percent
+const DIG_MESSINESS_CHOICES: [u8; 11] = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+
+/// Dig garbage rise choices, in seconds between rows (0 is off)
+const DIG_RISE_CHOICES: [u8; 11] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+/// One rule that can be changed
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Setting {
+    Randomizer,
+    RotationSystem,
+    Scoring,
+    Gravity,
+    MarathonLevelUp,
+    DigMessiness,
+    DigRise,
+}
+
+impl Setting {
+    const ALL: [Setting; 7] = [
+        Setting::Randomizer,
+        Setting::RotationSystem,
+        Setting::Scoring,
+        Setting::Gravity,
+        Setting::MarathonLevelUp,
+        Setting::DigMessiness,
+        Setting::DigRise,
+    ];
+
+    /// Text shown between the < and > buttons
+    fn label(self, options: &RuleOptions) -> String {
+        match self {
+            Setting::Randomizer => format!("Randomizer: {}", options.randomizer.name()),
+            Setting::RotationSystem => format!("Rotation: {}", options.rotation_system.name()),
+            Setting::Scoring => format!("Scoring: {}", options.scoring_rule.name()),
+            Setting::Gravity => format!("Gravity: {}", options.gravity.name()),
+            Setting::MarathonLevelUp => {
+                format!("Marathon Level Up: {}", options.marathon_level_up.name())
+            }
+            Setting::DigMessiness => format!("Dig Messiness: {}%", options.dig_messiness),
+            Setting::DigRise => match options.dig_rise_seconds {
+                0 => "Dig Rising Garbage: Off".to_string(),
+                seconds => format!("Dig Rising Garbage: {}s", seconds),
+            },
+        }
+    }
+
+    /// Move to the next (1) or previous (-1) choice
+    fn adjust(self, options: &mut RuleOptions, steps: i32) {
+        match self {
+            Setting::Randomizer => {
+                options.randomizer = cycle(&RandomizerKind::ALL, options.randomizer, steps)
+            }
+            Setting::RotationSystem => {
+                options.rotation_system =
+                    cycle(&RotationSystemKind::ALL, options.rotation_system, steps)
+            }
+            Setting::Scoring => {
+                options.scoring_rule = cycle(&ScoringRuleKind::ALL, options.scoring_rule, steps)
+            }
+            Setting::Gravity => {
+                options.gravity = cycle(&GravityKind::SELECTABLE, options.gravity, steps)
+            }
+            Setting::MarathonLevelUp => {
+                options.marathon_level_up =
+                    cycle(&LevelUpEffect::ALL, options.marathon_level_up, steps)
+            }
+            Setting::DigMessiness => {
+                options.dig_messiness = cycle(&DIG_MESSINESS_CHOICES, options.dig_messiness, steps)
+            }
+            Setting::DigRise => {
+                options.dig_rise_seconds = cycle(&DIG_RISE_CHOICES, options.dig_rise_seconds, steps)
+            }
+        }
+    }
+}
+
+/// Randomizer, rotation system, scoring and other rules, each with < / > buttons
+/// Changes are saved as soon as they're made and apply from the next game on
+pub struct RulesScreen {
+    setting_buttons: Vec<(Setting, Button, Button)>, // Setting with its < and > buttons
+    reset_button: Button,
+    back_button: Button,
+    options: RuleOptions,
+}
+
+impl RulesScreen {
+    /// Scale factor based on screen height, clamped to prevent extreme sizes
+    fn scale_factor(screen_height: f32) -> f32 {
+        (screen_height / 1048.0).clamp(0.5, 2.0)
+    }
+
+    /// Base row width, from the < button to the > button (normalized to 1048px height)
+    const BASE_ROW_WIDTH: f32 = 480.0;
+    /// Base size of the < / > buttons (normalized to 1048px height)
+    const BASE_STEP_BUTTON_SIZE: f32 = 50.0;
+    /// Base spacing between rows (normalized to 1048px height)
+    const BASE_ROW_STEP: f32 = 70.0;
+    /// Base Y position of the first row (normalized to 1048px height)
+    const BASE_FIRST_ROW_Y: f32 = -330.0;
+    /// Base Y position of the Reset / Back buttons (normalized to 1048px height)
+    const BASE_BUTTONS_Y: f32 = 190.0;
+    /// Base button height (normalized to 1048px height)
+    const BASE_BUTTON_HEIGHT: f32 = 50.0;
+    /// Base gap between the Reset and Back buttons (normalized to 1048px height)
+    const BASE_BUTTON_GAP: f32 = 20.0;
+
+    pub fn new() -> Self {
+        let mut screen = Self {
+            setting_buttons: Setting::ALL
+                .iter()
+                .map(|&setting| {
+                    (
+                        setting,
+                        Button::new(0.0, 0.0, 0.0, 0.0, "<"),
+                        Button::new(0.0, 0.0, 0.0, 0.0, ">"),
+                    )
+                })
+                .collect(),
+            reset_button: Button::new(0.0, 0.0, 0.0, 0.0, "Reset"),
+            back_button: Button::new(0.0, 0.0, 0.0, 0.0, "Back"),
+            options: Storage::load_rule_options(),
+        };
+        // Use default screen dimensions for initial layout (will be updated via update)
+        screen.update(640.0, 1048.0);
+        screen
+    }
+
+    /// Reload the options when the screen is opened
+    pub fn open(&mut self) {
+        self.options = Storage::load_rule_options();
+    }
+
+    /// Update button positions and sizes based on actual screen dimensions
+    pub fn update(&mut self, _screen_width: f32, screen_height: f32) {
+        let scale = Self::scale_factor(screen_height);
+        let width = Self::BASE_ROW_WIDTH * scale;
+        let button_size = Self::BASE_STEP_BUTTON_SIZE * scale;
+        let left_x = -width / 2.0;
+
+        for (index, (_, previous_button, next_button)) in
+            self.setting_buttons.iter_mut().enumerate()
+        {
+            let row_y = (Self::BASE_FIRST_ROW_Y + Self::BASE_ROW_STEP * index as f32) * scale;
+            previous_button.set_position(left_x, row_y, button_size, button_size);
+            next_button.set_position(
+                left_x + width - button_size,
+                row_y,
+                button_size,
+                button_size,
+            );
+        }
+
+        // Reset and Back side by side under the rows
+        let button_height = Self::BASE_BUTTON_HEIGHT * scale;
+        let half_width = (width - Self::BASE_BUTTON_GAP * scale) / 2.0;
+        let buttons_y = Self::BASE_BUTTONS_Y * scale;
+        self.reset_button
+            .set_position(left_x, buttons_y, half_width, button_height);
+        self.back_button.set_position(
+            left_x + width - half_width,
+            buttons_y,
+            half_width,
+            button_height,
+        );
+    }
+
+    /// Handle input for the rules screen
+    /// Returns true when the player leaves the screen
+    pub fn handle_input(&mut self, input: &Input, screen_width: f32, screen_height: f32) -> bool {
+        let mut changed = false;
+        for (setting, previous_button, next_button) in &self.setting_buttons {
+            if previous_button.is_clicked(input, screen_width, screen_height) {
+                setting.adjust(&mut self.options, -1);
+                changed = true;
+            } else if next_button.is_clicked(input, screen_width, screen_height) {
+                setting.adjust(&mut self.options, 1);
+                changed = true;
+            }
+        }
+
+        if self
+            .reset_button
+            .is_clicked(input, screen_width, screen_height)
+        {
+            self.options = RuleOptions::default();
+            changed = true;
+        }
+
+        if changed {
+            Storage::save_rule_options(&self.options);
+            println!("Rules: {:?}", self.options);
+            return false;
+        }
+
+        input.key_pressed(KeyCode::Escape)
+            || self
+                .back_button
+                .is_clicked(input, screen_width, screen_height)
+    }
+
+    pub fn draw(&self, gfx: &mut Graphics, screen_width: f32, screen_height: f32) {
+        let coords = CoordinateSystem::with_default_offset(screen_width, screen_height);
+        let scale = Self::scale_factor(screen_height);
+
+        // Same backdrop as the settings screen
+        gfx.rect()
+            .at(coords.top_left_world())
+            .size(vec2(screen_width, screen_height))
+            .color(COLOR_DARK_GRAY);
+
+        let title_size = (screen_height * 0.046).clamp(32.0, 80.0);
+        Self::draw_centered_text(gfx, &coords, "RULES", -430.0 * scale, title_size);
+
+        // Value labels sit between each row's < and > buttons, vertically centered
+        let label_size = (screen_height * 0.023).clamp(18.0, 40.0);
+        let button_size = Self::BASE_STEP_BUTTON_SIZE * scale;
+        for (index, (setting, previous_button, next_button)) in
+            self.setting_buttons.iter().enumerate()
+        {
+            let row_y = (Self::BASE_FIRST_ROW_Y + Self::BASE_ROW_STEP * index as f32) * scale;
+            let label = setting.label(&self.options);
+            Self::draw_centered_text(
+                gfx,
+                &coords,
+                &label,
+                row_y + (button_size - label_size) / 2.0,
+                label_size,
+            );
+            previous_button.draw(gfx, screen_width, screen_height);
+            next_button.draw(gfx, screen_width, screen_height);
+        }
+
+        self.reset_button.draw(gfx, screen_width, screen_height);
+        self.back_button.draw(gfx, screen_width, screen_height);
+
+        let hint_size = (screen_height * 0.018).clamp(12.0, 36.0);
+        Self::draw_centered_text(
+            gfx,
+            &coords,
+            "Changes apply from the next game",
+            270.0 * scale,
+            hint_size,
+        );
+    }
+
+    /// Draw a line of text centered horizontally at a world Y position
+    fn draw_centered_text(
+        gfx: &mut Graphics,
+        coords: &CoordinateSystem,
+        text: &str,
+        world_y: f32,
+        size: f32,
+    ) {
+        let world_x = coords.center_text_x(text, size, 0.5);
+        let screen_pos = coords.world_to_screen(vec2(world_x, world_y));
+        gfx.text(text)
+            .at(screen_pos)
+            .size(size)
+            .color(COLOR_TEXT_GREEN);
+    }
+}
+
+impl Default for RulesScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,175 @@
+use crate::engine::{DEFAULT_LOCK_DELAY, DEFAULT_MAX_LOCK_RESETS};
+use crate::garbage::GarbageSettings;
+use crate::gravity::GravityKind;
+use crate::randomizer::RandomizerKind;
+use crate::rotation::RotationSystemKind;
+use crate::scoring::ScoringRuleKind;
+use serde::{Deserialize, Serialize};
+
+/// Fewest upcoming pieces the next queue can show
+pub const MIN_PREVIEW_COUNT: usize = 1;
+
+/// Most upcoming pieces the next queue can show
+pub const MAX_PREVIEW_COUNT: usize = 6;
+
+/// What happens to the board when the level goes up
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LevelUpEffect {
+    /// The stack cascades off the screen and the next level starts on an empty board (Retris)
+    #[default]
+    Wipe,
+    /// The board keeps its blocks and just flashes (classic marathon)
+    Flash,
+}
+
+impl LevelUpEffect {
+    /// Every effect, in the order the rules screen cycles through them
+    pub const ALL: [LevelUpEffect; 2] = [LevelUpEffect::Wipe, LevelUpEffect::Flash];
+
+    /// Name shown on the rules screen
+    pub fn name(self) -> &'static str {
+        match self {
+            LevelUpEffect::Wipe => "Wipe",
+            LevelUpEffect::Flash => "Flash",
+        }
+    }
+}
+
+/// What finishes a game, other than topping out
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Goal {
+    /// Play until the stack tops out (marathon)
+    #[default]
+    Endless,
+    /// Clear this many lines as fast as possible (sprint)
+    Lines(u16),
+    /// Score as much as possible before this many seconds run out (ultra)
+    TimeLimit(u32),
+    /// Clear this many garbage lines as fast as possible (dig)
+    GarbageLines(u16),
+}
+
+/// Gameplay rules chosen when a game starts
+/// Everything that changes how a run plays out lives here so games can be configured per mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ruleset {
+    /// How the next piece is chosen
+    pub randomizer: RandomizerKind,
+    /// How pieces rotate and get kicked off walls and the floor
+    pub rotation_system: RotationSystemKind,
+    /// How clears are turned into points (high scores are kept per rule)
+    pub scoring_rule: ScoringRuleKind,
+    /// Number of upcoming pieces shown in the next queue (clamped to 1-6)
+    pub preview_count: usize,
+    /// Seconds a piece can rest on the stack before locking
+    pub lock_delay: f32,
+    /// How many moves/rotations can restart the lock delay (prevents infinite stalling)
+    pub max_lock_resets: u32,
+    /// Fall speed curve across levels
+    pub gravity: GravityKind,
+    /// Whether leveling up wipes the board or only flashes it
+    pub level_up_effect: LevelUpEffect,
+    /// What finishes the game (replays from before goals existed are endless)
+    #[serde(default)]
+    pub goal: Goal,
+    /// Garbage on the board at the start and over time (None starts on an empty board)
+    #[serde(default)]
+    pub garbage: Option<GarbageSettings>,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self {
+            randomizer: RandomizerKind::default(),
+            rotation_system: RotationSystemKind::default(),
+            scoring_rule: ScoringRuleKind::default(),
+            preview_count: 5,
+            lock_delay: DEFAULT_LOCK_DELAY,
+            max_lock_resets: DEFAULT_MAX_LOCK_RESETS,
+            gravity: GravityKind::default(),
+            level_up_effect: LevelUpEffect::default(),
+            goal: Goal::default(),
+            garbage: None,
+        }
+    }
+}
+
+impl Ruleset {
+    /// Key the personal best time is stored under (None for untimed goals)
+    /// Dig times are kept apart for each garbage setup
+    pub fn best_time_key(&self) -> Option<String> {
+        match self.goal {
+            Goal::Lines(lines) => Some(format!("sprint_{}", lines)),
+            Goal::GarbageLines(lines) => Some(self.dig_key(lines)),
+            Goal::Endless | Goal::TimeLimit(_) => None,
+        }
+    }
+
+    /// Dig mode part of a storage key, apart for each garbage setup
+    fn dig_key(&self, lines: u16) -> String {
+        match &self.garbage {
+            Some(garbage) => format!("dig_{}_{}", lines, garbage.storage_key()),
+            None => format!("dig_{}", lines),
+        }
+    }
+
+    /// Key the best score is stored under, when it isn't the regular high score
+    /// Only endless games share the regular high score; every other goal keeps its own,
+    /// one per scoring rule (and per garbage setup for dig, like its best time)
+    pub fn best_score_key(&self) -> Option<String> {
+        let mode = match self.goal {
+            Goal::Endless => return None,
+            Goal::Lines(lines) => format!("sprint_{}", lines),
+            Goal::TimeLimit(seconds) => format!("ultra_{}", seconds),
+            Goal::GarbageLines(lines) => self.dig_key(lines),
+        };
+        Some(format!("{}_{}", mode, self.scoring_rule.storage_key()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dig(messiness: f32) -> Ruleset {
+        Ruleset {
+            goal: Goal::GarbageLines(10),
+            garbage: Some(GarbageSettings {
+                starting_rows: 10,
+                messiness,
+                rise_interval: None,
+            }),
+            ..Ruleset::default()
+        }
+    }
+
+    #[test]
+    fn only_endless_games_use_the_regular_high_score() {
+        let with_goal = |goal| Ruleset {
+            goal,
+            ..Ruleset::default()
+        };
+        assert_eq!(with_goal(Goal::Endless).best_score_key(), None);
+        assert_eq!(
+            with_goal(Goal::Lines(40)).best_score_key().as_deref(),
+            Some("sprint_40_retris")
+        );
+        assert_eq!(
+            with_goal(Goal::TimeLimit(120)).best_score_key().as_deref(),
+            Some("ultra_120_retris")
+        );
+    }
+
+    #[test]
+    fn dig_scores_and_times_are_kept_per_garbage_setup() {
+        assert_eq!(
+            dig(0.3).best_score_key().as_deref(),
+            Some("dig_10_mess30_rise0_retris")
+        );
+        assert_eq!(
+            dig(0.3).best_time_key().as_deref(),
+            Some("dig_10_mess30_rise0")
+        );
+        assert_ne!(dig(0.3).best_score_key(), dig(0.5).best_score_key());
+    }
+}
@@ -0,0 +1,500 @@
+use crate::game_data::{ClearInfo, TSpin};
+use serde::{Deserialize, Serialize};
+
+/// Everything a scoring rule may look at when pricing a clear
+#[derive(Debug, Clone, Copy)]
+pub struct ClearContext {
+    /// What the locked piece did
+    pub clear: ClearInfo,
+    /// Level at the moment of the clear (before any level up it causes)
+    pub level: u16,
+    /// Consecutive clears including this one (1 = first clear of a chain)
+    pub combo_count: u32,
+    /// Multiplier carried over from the previous clear
+    pub previous_multiplier: u32,
+    /// True if this clear continues a chain of difficult clears (Tetris / T-spin)
+    pub back_to_back: bool,
+}
+
+/// Points for a clear, and the multiplier carried into the next clear
+#[derive(Debug, Clone, Copy)]
+pub struct ClearScore {
+    pub points: u64,
+    pub next_multiplier: u32,
+}
+
+/// Turns clears and drops into points
+/// Score bookkeeping (lines, level, combo and back-to-back tracking) stays in `ScoreManager`
+pub trait ScoringRule {
+    /// Price a clear; called for every clear and for zero-line T-spins
+    fn score_clear(&self, context: &ClearContext) -> ClearScore;
+
+    /// Points for hard dropping a piece a number of cells
+    fn hard_drop_points(&self, cells_dropped: u32) -> u64;
+}
+
+/// Available scoring rules, picked per mode
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ScoringRuleKind {
+    /// Retris' own exponential formula
+    #[default]
+    Retris,
+    /// Modern Guideline scoring
+    Guideline,
+    /// Classic NES scoring
+    Nes,
+}
+
+impl ScoringRuleKind {
+    /// Every scoring rule, in the order the rules screen cycles through them
+    pub const ALL: [ScoringRuleKind; 3] = [
+        ScoringRuleKind::Retris,
+        ScoringRuleKind::Guideline,
+        ScoringRuleKind::Nes,
+    ];
+
+    /// Name shown on the rules screen
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoringRuleKind::Retris => "Retris",
+            ScoringRuleKind::Guideline => "Guideline",
+            ScoringRuleKind::Nes => "NES",
+        }
+    }
+
+    /// Create the scoring rule of this kind
+    pub fn create(self) -> Box<dyn ScoringRule> {
+        match self {
+            ScoringRuleKind::Retris => Box::new(RetrisScoring),
+            ScoringRuleKind::Guideline => Box::new(GuidelineScoring),
+            ScoringRuleKind::Nes => Box::new(NesScoring),
+        }
+    }
+
+    /// Key the rule's high score is stored under
+    pub fn storage_key(self) -> &'static str {
+        match self {
+            ScoringRuleKind::Retris => "retris",
+            ScoringRuleKind::Guideline => "guideline",
+            ScoringRuleKind::Nes => "nes",
+        }
+    }
+}
+
+// ============================================================================
+// Retris
+// ============================================================================
+
+/// The Retris scoring system with EXPONENTIAL multipliers
+///
+/// ## Scoring Formula:
+/// `points = 137 × rows_bonus × previous_multiplier × combo_multiplier × level_multiplier`
+///
+/// - **Base Points**: 137 (prime number for interesting scores)
+///
+/// - **Rows Bonus** (exponential - 2^n - 1):
+///   - 1 row: 1x
+///   - 2 rows: 3x
+///   - 3 rows: 7x
+///   - 4 rows (Tetris!): 15x 🔥
+///
+/// - **Previous Multiplier**: Carries over from last clear (starts at 1x)
+///   - Clear a Tetris → next clear gets 15x multiplier!
+///   - This compounds with the next clear's rows_bonus
+///
+/// - **Combo Multiplier** (exponential - 2^(combo-1)):
+///   - 1st consecutive clear: 1x
+///   - 2nd consecutive clear: 2x
+///   - 3rd consecutive clear: 4x
+///   - 4th consecutive clear: 8x
+///   - 5th consecutive clear: 16x 💥
+///   - Resets to 0 if a piece lands without clearing
+///
+/// - **Level Multiplier**: Rewards survival at high speeds!
+///   - Level 0-4: 1x
+///   - Level 5-9: 2x
+///   - Level 10-14: 3x
+///   - Level 15-19: 5x
+///   - Level 20+: 8x 🚀
+///
+/// - **T-Spins**: Count as extra rows in the rows bonus (mini +1, full +2)
+///   - T-Spin Single: 7x, T-Spin Double: 15x, T-Spin Triple: 31x
+///   - A T-spin without lines scores 137 × (mini 1x, full 3x) × level_multiplier
+///     and leaves combo and multiplier alone
+///
+/// - **Back-to-Back**: Difficult clears (Tetrises and T-spins with lines) in a row score 2x
+///   - Pieces that clear nothing keep the chain going, any easy clear (1-3 rows) breaks it
+///
+/// - **Perfect Clear**: Emptying the whole board multiplies the clear by 16x (2^4) 🏆
+///   - Level 0, Perfect clear Tetris: 137 × 15 × 1 × 1 × 1 × 16 = 32,880 points!
+///
+/// - **Hard Drop**: 2 points per cell dropped, independent of multipliers
+///
+/// All math saturates at `u64::MAX` instead of overflowing, so absurd combos just max out
+///
+/// ## Example Scoring Sequences:
+/// - Level 0, Clear 1 row: 137 × 1 × 1 × 1 × 1 = 137 points
+/// - Level 0, Clear 4 rows (Tetris): 137 × 15 × 1 × 1 × 1 = 2,055 points
+/// - Level 0, Clear 4 more rows (combo!): 137 × 15 × 15 × 2 × 1 = 61,650 points! 🤯
+/// - Level 10, Clear 4 rows (Tetris): 137 × 15 × 1 × 1 × 3 = 6,165 points!
+/// - Level 20, Clear 4 rows (Tetris): 137 × 15 × 1 × 1 × 8 = 16,440 points!! 💰
+/// - Level 20, Clear 4 more (combo!): 137 × 15 × 15 × 2 × 8 = 493,200 points!!! 🔥💥🚀
+pub struct RetrisScoring;
+
+impl RetrisScoring {
+    /// Base points per row (a prime number for more interesting scores)
+    const BASE_POINTS_PER_ROW: u64 = 137;
+
+    /// Level multiplier - reward players for surviving at high levels
+    pub fn level_multiplier(level: u16) -> u64 {
+        match level {
+            0..=4 => 1,
+            5..=9 => 2,
+            10..=14 => 3,
+            15..=19 => 5,
+            _ => 8, // Level 20+
+        }
+    }
+}
+
+impl ScoringRule for RetrisScoring {
+    fn score_clear(&self, context: &ClearContext) -> ClearScore {
+        let clear = context.clear;
+        let level_multiplier = Self::level_multiplier(context.level);
+
+        if clear.rows == 0 {
+            // Zero-line T-spins are worth a little, everything else scores nothing
+            let t_spin_bonus = match clear.t_spin {
+                TSpin::None => 0,
+                TSpin::Mini => 1,
+                TSpin::Full => 3,
+            };
+            return ClearScore {
+                points: Self::BASE_POINTS_PER_ROW
+                    .saturating_mul(t_spin_bonus)
+                    .saturating_mul(level_multiplier),
+                next_multiplier: context.previous_multiplier,
+            };
+        }
+
+        // Rows bonus - exponential based on rows cleared at once:
+        //    - 1 row: 1x (base)
+        //    - 2 rows: 3x (1.5x per row)
+        //    - 3 rows: 7x (2.3x per row)
+        //    - 4 rows (Tetris!): 15x (3.75x per row!)
+        //    T-spins count as extra rows: a mini adds one, a full T-spin adds two
+        let bonus_rows = clear.rows
+            + match clear.t_spin {
+                TSpin::None => 0,
+                TSpin::Mini => 1,
+                TSpin::Full => 2,
+            };
+        let rows_bonus_multiplier = match bonus_rows {
+            1 => 1,
+            2 => 3,
+            3 => 7,
+            4 => 15,
+            // 2^n - 1 for higher
+            _ => 1u64
+                .checked_shl(bonus_rows as u32)
+                .map_or(u64::MAX, |power| power - 1),
+        };
+
+        // Combo multiplier - gets exponential with chain length (2^(combo-1)):
+        //    Combo 1: 1x, Combo 2: 2x, Combo 3: 4x, Combo 4: 8x, Combo 5: 16x (insane!)
+        //    Long enough chains saturate instead of overflowing the shift
+        let combo_multiplier = 1u64
+            .checked_shl(context.combo_count.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+
+        let back_to_back_multiplier = if context.back_to_back { 2 } else { 1 };
+        let perfect_clear_multiplier = if clear.perfect_clear { 16 } else { 1 };
+
+        // Final formula: base × rows_bonus × previous_multiplier × combo_multiplier × level_multiplier
+        //                × back_to_back × perfect_clear
+        let points = Self::BASE_POINTS_PER_ROW
+            .saturating_mul(rows_bonus_multiplier)
+            .saturating_mul(context.previous_multiplier as u64)
+            .saturating_mul(combo_multiplier)
+            .saturating_mul(level_multiplier)
+            .saturating_mul(back_to_back_multiplier)
+            .saturating_mul(perfect_clear_multiplier);
+
+        // Set the multiplier for the NEXT clear based on this clear
+        // This creates insane compounding:
+        // - Clear 4 rows -> 15x multiplier for next clear
+        // - Clear 4 more rows -> 15x on THAT too!
+        ClearScore {
+            points,
+            next_multiplier: u32::try_from(rows_bonus_multiplier).unwrap_or(u32::MAX),
+        }
+    }
+
+    fn hard_drop_points(&self, cells_dropped: u32) -> u64 {
+        2 * cells_dropped as u64
+    }
+}
+
+// ============================================================================
+// Guideline
+// ============================================================================
+
+/// Modern Guideline scoring: fixed values per clear type, times the level
+/// - Single 100, Double 300, Triple 500, Tetris 800
+/// - T-spin 400 / 800 / 1200 / 1600 (0-3 lines), mini 100 / 200 / 400 (0-2 lines)
+/// - Back-to-back difficult clears are worth 1.5x
+/// - Combos add 50 × combo × level; perfect clears add 800 / 1200 / 1800 / 2000 × level
+///   (3200 for a back-to-back Tetris)
+/// - Hard drop: 2 points per cell
+pub struct GuidelineScoring;
+
+impl ScoringRule for GuidelineScoring {
+    fn score_clear(&self, context: &ClearContext) -> ClearScore {
+        let clear = context.clear;
+        // Guideline levels start at 1
+        let level = context.level as u64 + 1;
+
+        let action_points: u64 = match (clear.t_spin, clear.rows) {
+            (TSpin::None, 0) => 0,
+            (TSpin::None, 1) => 100,
+            (TSpin::None, 2) => 300,
+            (TSpin::None, 3) => 500,
+            (TSpin::None, _) => 800,
+            (TSpin::Mini, 0) => 100,
+            (TSpin::Mini, 1) => 200,
+            (TSpin::Mini, _) => 400,
+            (TSpin::Full, 0) => 400,
+            (TSpin::Full, 1) => 800,
+            (TSpin::Full, 2) => 1200,
+            (TSpin::Full, _) => 1600,
+        };
+        let mut points = action_points.saturating_mul(level);
+        if context.back_to_back {
+            points = points.saturating_mul(3) / 2;
+        }
+
+        if clear.rows > 0 {
+            // Combo counter starts at 0 on the first clear of a chain
+            let combo = context.combo_count.saturating_sub(1) as u64;
+            points = points.saturating_add(50u64.saturating_mul(combo).saturating_mul(level));
+        }
+
+        if clear.perfect_clear {
+            let perfect_clear_points: u64 = match clear.rows {
+                1 => 800,
+                2 => 1200,
+                3 => 1800,
+                _ if context.back_to_back => 3200,
+                _ => 2000,
+            };
+            points = points.saturating_add(perfect_clear_points.saturating_mul(level));
+        }
+
+        ClearScore {
+            points,
+            next_multiplier: 1,
+        }
+    }
+
+    fn hard_drop_points(&self, cells_dropped: u32) -> u64 {
+        2 * cells_dropped as u64
+    }
+}
+
+// ============================================================================
+// NES
+// ============================================================================
+
+/// Classic NES scoring: 40 / 100 / 300 / 1200 × (level + 1)
+/// No T-spins, back-to-back or perfect clear bonuses, and no points for hard drops
+pub struct NesScoring;
+
+impl ScoringRule for NesScoring {
+    fn score_clear(&self, context: &ClearContext) -> ClearScore {
+        let line_points: u64 = match context.clear.rows {
+            0 => 0,
+            1 => 40,
+            2 => 100,
+            3 => 300,
+            _ => 1200,
+        };
+        ClearScore {
+            points: line_points.saturating_mul(context.level as u64 + 1),
+            next_multiplier: 1,
+        }
+    }
+
+    fn hard_drop_points(&self, _cells_dropped: u32) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_data::ScoreManager;
+
+    fn context(rows: u16, t_spin: TSpin, level: u16) -> ClearContext {
+        ClearContext {
+            clear: ClearInfo {
+                rows,
+                t_spin,
+                perfect_clear: false,
+            },
+            level,
+            combo_count: if rows > 0 { 1 } else { 0 },
+            previous_multiplier: 1,
+            back_to_back: false,
+        }
+    }
+
+    fn points(rule: &dyn ScoringRule, context: ClearContext) -> u64 {
+        rule.score_clear(&context).points
+    }
+
+    #[test]
+    fn retris_rows_bonus_and_level_multiplier() {
+        assert_eq!(points(&RetrisScoring, context(1, TSpin::None, 0)), 137);
+        assert_eq!(points(&RetrisScoring, context(2, TSpin::None, 0)), 411);
+        assert_eq!(points(&RetrisScoring, context(3, TSpin::None, 0)), 959);
+        assert_eq!(points(&RetrisScoring, context(4, TSpin::None, 0)), 2_055);
+        assert_eq!(points(&RetrisScoring, context(4, TSpin::None, 10)), 6_165);
+        assert_eq!(points(&RetrisScoring, context(4, TSpin::None, 20)), 16_440);
+    }
+
+    #[test]
+    fn retris_carries_the_rows_bonus_into_the_next_clear() {
+        let tetris = RetrisScoring.score_clear(&context(4, TSpin::None, 0));
+        assert_eq!(tetris.next_multiplier, 15);
+
+        let follow_up = ClearContext {
+            combo_count: 2,
+            previous_multiplier: tetris.next_multiplier,
+            ..context(4, TSpin::None, 0)
+        };
+        assert_eq!(points(&RetrisScoring, follow_up), 61_650);
+        assert_eq!(
+            points(
+                &RetrisScoring,
+                ClearContext {
+                    level: 20,
+                    ..follow_up
+                }
+            ),
+            493_200
+        );
+    }
+
+    #[test]
+    fn retris_t_spins_back_to_back_and_perfect_clears() {
+        assert_eq!(points(&RetrisScoring, context(0, TSpin::Mini, 0)), 137);
+        assert_eq!(points(&RetrisScoring, context(0, TSpin::Full, 0)), 411);
+        assert_eq!(points(&RetrisScoring, context(1, TSpin::Full, 0)), 959);
+        assert_eq!(points(&RetrisScoring, context(2, TSpin::Full, 0)), 2_055);
+
+        let mut tetris = context(4, TSpin::None, 0);
+        tetris.back_to_back = true;
+        assert_eq!(points(&RetrisScoring, tetris), 4_110);
+        tetris.back_to_back = false;
+        tetris.clear.perfect_clear = true;
+        assert_eq!(points(&RetrisScoring, tetris), 32_880);
+
+        // A zero-line T-spin keeps the multiplier it was handed
+        let spin = ClearContext {
+            previous_multiplier: 7,
+            ..context(0, TSpin::Full, 0)
+        };
+        assert_eq!(RetrisScoring.score_clear(&spin).next_multiplier, 7);
+    }
+
+    #[test]
+    fn guideline_point_table() {
+        let level = 0;
+        let table = [
+            (0, TSpin::None, 0),
+            (1, TSpin::None, 100),
+            (2, TSpin::None, 300),
+            (3, TSpin::None, 500),
+            (4, TSpin::None, 800),
+            (0, TSpin::Mini, 100),
+            (1, TSpin::Mini, 200),
+            (2, TSpin::Mini, 400),
+            (0, TSpin::Full, 400),
+            (1, TSpin::Full, 800),
+            (2, TSpin::Full, 1_200),
+            (3, TSpin::Full, 1_600),
+        ];
+        for (rows, t_spin, expected) in table {
+            assert_eq!(
+                points(&GuidelineScoring, context(rows, t_spin, level)),
+                expected,
+                "{rows} rows, {t_spin:?}"
+            );
+        }
+        // Levels start at 1, so the engine's level 2 is Guideline level 3
+        assert_eq!(points(&GuidelineScoring, context(4, TSpin::None, 2)), 2_400);
+    }
+
+    #[test]
+    fn guideline_bonuses() {
+        let mut tetris = context(4, TSpin::None, 0);
+        tetris.back_to_back = true;
+        assert_eq!(points(&GuidelineScoring, tetris), 1_200);
+        tetris.clear.perfect_clear = true;
+        assert_eq!(points(&GuidelineScoring, tetris), 1_200 + 3_200);
+
+        let mut single = context(1, TSpin::None, 0);
+        single.clear.perfect_clear = true;
+        assert_eq!(points(&GuidelineScoring, single), 100 + 800);
+
+        // Third clear in a row: combo counter 2
+        let combo = ClearContext {
+            combo_count: 3,
+            ..context(1, TSpin::None, 1)
+        };
+        assert_eq!(points(&GuidelineScoring, combo), 200 + 50 * 2 * 2);
+        assert_eq!(GuidelineScoring.hard_drop_points(10), 20);
+    }
+
+    #[test]
+    fn nes_point_table() {
+        for (rows, expected) in [(0, 0), (1, 40), (2, 100), (3, 300), (4, 1_200)] {
+            assert_eq!(points(&NesScoring, context(rows, TSpin::None, 0)), expected);
+            assert_eq!(
+                points(&NesScoring, context(rows, TSpin::None, 9)),
+                expected * 10
+            );
+        }
+        // No T-spin, back-to-back or perfect clear bonuses, and no drop points
+        let mut spin = context(2, TSpin::Full, 0);
+        spin.back_to_back = true;
+        spin.clear.perfect_clear = true;
+        assert_eq!(points(&NesScoring, spin), 100);
+        assert_eq!(points(&NesScoring, context(0, TSpin::Full, 0)), 0);
+        assert_eq!(NesScoring.hard_drop_points(10), 0);
+    }
+
+    #[test]
+    fn long_combos_saturate_instead_of_overflowing() {
+        let clear = ClearInfo {
+            rows: 4,
+            t_spin: TSpin::None,
+            perfect_clear: true,
+        };
+        for rule in ScoringRuleKind::ALL {
+            let mut score_manager = ScoreManager::new(rule);
+            for _ in 0..100 {
+                score_manager.on_rows_cleared(clear);
+            }
+            assert_eq!(score_manager.combo_count(), 100);
+            assert!(score_manager.score() > 0);
+        }
+
+        // The Retris combo multiplier doubles every clear, so it's the first to top out
+        let mut score_manager = ScoreManager::new(ScoringRuleKind::Retris);
+        for _ in 0..100 {
+            score_manager.on_rows_cleared(clear);
+        }
+        assert_eq!(score_manager.score(), u64::MAX);
+    }
+}
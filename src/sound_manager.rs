@@ -15,6 +15,10 @@ struct SoundEffects {
     level_up: Option<StaticSoundData>,
     shuffle: Option<StaticSoundData>,
     success: Option<StaticSoundData>,
+    hard_drop: Option<StaticSoundData>,
+    shuffle_counter_clockwise: Option<StaticSoundData>,
+    shuffle_180: Option<StaticSoundData>,
+    t_spin: Option<StaticSoundData>,
 }
 
 impl SoundManager {
@@ -37,6 +41,13 @@ impl SoundManager {
         let level_up = Self::load_audio_data_from_bytes(include_bytes!("../assets/level-up.ogg")).ok();
         let shuffle = Self::load_audio_data_from_bytes(include_bytes!("../assets/shufle.ogg")).ok(); // Note: filename has typo "shufle"
         let success = Self::load_audio_data_from_bytes(include_bytes!("../assets/success.ogg")).ok();
+        // Hard drop reuses the bounce sample pitched down for a heavier slam
+        let hard_drop = bounce.clone().map(|sound| sound.playback_rate(0.6));
+        // Other rotation directions reuse the shuffle sample at a different pitch
+        let shuffle_counter_clockwise = shuffle.clone().map(|sound| sound.playback_rate(0.8));
+        let shuffle_180 = shuffle.clone().map(|sound| sound.playback_rate(1.3));
+        // T-spins get a brighter, faster take on the success sound
+        let t_spin = success.clone().map(|sound| sound.playback_rate(1.5));
 
         if bounce.is_some() {
             println!("Loaded bounce sound");
@@ -66,6 +77,10 @@ impl SoundManager {
                 level_up,
                 shuffle,
                 success,
+                hard_drop,
+                shuffle_counter_clockwise,
+                shuffle_180,
+                t_spin,
             },
             muted: false,
             volume_manager,
@@ -133,6 +148,24 @@ impl SoundManager {
         }
     }
 
+    /// Play lower shuffle sound (piece rotates counter-clockwise)
+    pub fn play_shuffle_counter_clockwise(&mut self) {
+        if !self.muted {
+            if let Some(ref sound) = self.sounds.shuffle_counter_clockwise {
+                let _ = self.audio_manager.play(sound.clone());
+            }
+        }
+    }
+
+    /// Play higher shuffle sound (piece rotates 180 degrees)
+    pub fn play_shuffle_180(&mut self) {
+        if !self.muted {
+            if let Some(ref sound) = self.sounds.shuffle_180 {
+                let _ = self.audio_manager.play(sound.clone());
+            }
+        }
+    }
+
     /// Play success sound (lines cleared)
     pub fn play_success(&mut self) {
         if !self.muted {
@@ -142,6 +175,24 @@ impl SoundManager {
         }
     }
 
+    /// Play hard drop sound (piece slammed down)
+    pub fn play_hard_drop(&mut self) {
+        if !self.muted {
+            if let Some(ref sound) = self.sounds.hard_drop {
+                let _ = self.audio_manager.play(sound.clone());
+            }
+        }
+    }
+
+    /// Play T-spin sound (T piece spun into place)
+    pub fn play_t_spin(&mut self) {
+        if !self.muted {
+            if let Some(ref sound) = self.sounds.t_spin {
+                let _ = self.audio_manager.play(sound.clone());
+            }
+        }
+    }
+
     /// Set whether sound effects are muted
     pub fn set_muted(&mut self, muted: bool) {
         self.muted = muted;
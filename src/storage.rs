@@ -1,4 +1,12 @@
+use crate::controls::ControlBindings;
+use crate::handling::HandlingProfile;
+use crate::randomizer::RNG_ALGORITHM;
+use crate::replay::{REPLAY_VERSION, Replay};
+use crate::rule_options::RuleOptions;
+use crate::scoring::ScoringRuleKind;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,13 +26,95 @@ impl Default for VolumeSettings {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameData {
+    /// High score with Retris scoring (kept as its own field so older saves still load)
     pub high_score: u64,
+    /// High scores for the other scoring rules, by `ScoringRuleKind::storage_key`
+    #[serde(default)]
+    pub high_scores: HashMap<String, u64>,
 }
 
 impl Default for GameData {
     fn default() -> Self {
         Self {
             high_score: 0,
+            high_scores: HashMap::new(),
+        }
+    }
+}
+
+impl GameData {
+    /// High score recorded with a scoring rule
+    pub fn high_score_for(&self, scoring_rule: ScoringRuleKind) -> u64 {
+        match scoring_rule {
+            ScoringRuleKind::Retris => self.high_score,
+            _ => self
+                .high_scores
+                .get(scoring_rule.storage_key())
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Record a high score for a scoring rule
+    pub fn set_high_score_for(&mut self, scoring_rule: ScoringRuleKind, high_score: u64) {
+        match scoring_rule {
+            ScoringRuleKind::Retris => self.high_score = high_score,
+            _ => {
+                self.high_scores
+                    .insert(scoring_rule.storage_key().to_string(), high_score);
+            }
+        }
+    }
+}
+
+/// Gameplay preferences that aren't tied to a single game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameplaySettings {
+    pub show_ghost: bool,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self { show_ghost: true }
+    }
+}
+
+/// Best results in modes that are played against the clock
+/// Kept apart from `GameData`, whose high scores are for endless games
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersonalBests {
+    /// Fastest finish in milliseconds, by `Ruleset::best_time_key`
+    pub best_times: HashMap<String, u64>,
+    /// Highest score, by `Ruleset::best_score_key`
+    pub best_scores: HashMap<String, u64>,
+}
+
+impl PersonalBests {
+    /// Fastest recorded finish for a goal
+    pub fn best_time(&self, key: &str) -> Option<u64> {
+        self.best_times.get(key).copied()
+    }
+
+    /// Record a finish, returning true if it beat the previous best
+    pub fn record_time(&mut self, key: &str, millis: u64) -> bool {
+        if self.best_time(key).is_some_and(|best| best <= millis) {
+            return false;
+        }
+        self.best_times.insert(key.to_string(), millis);
+        true
+    }
+
+    /// Highest recorded score for a goal (0 if never played)
+    pub fn best_score(&self, key: &str) -> u64 {
+        self.best_scores.get(key).copied().unwrap_or(0)
+    }
+
+    /// Keep a score if it beats the previous best
+    pub fn set_best_score(&mut self, key: &str, score: u64) {
+        if score > self.best_score(key) {
+            self.best_scores.insert(key.to_string(), score);
         }
     }
 }
@@ -32,6 +122,11 @@ impl Default for GameData {
 // Static caches for loaded data (declared after types are defined)
 static VOLUME_CACHE: Mutex<Option<VolumeSettings>> = Mutex::new(None);
 static GAME_DATA_CACHE: Mutex<Option<GameData>> = Mutex::new(None);
+static GAMEPLAY_SETTINGS_CACHE: Mutex<Option<GameplaySettings>> = Mutex::new(None);
+static CONTROL_BINDINGS_CACHE: Mutex<Option<ControlBindings>> = Mutex::new(None);
+static HANDLING_CACHE: Mutex<Option<HandlingProfile>> = Mutex::new(None);
+static PERSONAL_BESTS_CACHE: Mutex<Option<PersonalBests>> = Mutex::new(None);
+static RULE_OPTIONS_CACHE: Mutex<Option<RuleOptions>> = Mutex::new(None);
 
 /// Platform-agnostic storage for game settings
 pub struct Storage;
@@ -150,12 +245,274 @@ impl Storage {
         }
     }
     
+    /// Load gameplay settings from storage (ghost piece, etc.)
+    /// Results are cached after first load for performance
+    pub fn load_gameplay_settings() -> GameplaySettings {
+        // Check cache first
+        if let Ok(cache) = GAMEPLAY_SETTINGS_CACHE.lock() {
+            if let Some(cached) = cache.as_ref() {
+                return cached.clone();
+            }
+        }
+
+        let settings: GameplaySettings =
+            Self::load_json(Self::GAMEPLAY_SETTINGS_KEY, "gameplay.json").unwrap_or_default();
+
+        // Update cache
+        if let Ok(mut cache) = GAMEPLAY_SETTINGS_CACHE.lock() {
+            *cache = Some(settings.clone());
+        }
+
+        settings
+    }
+
+    /// Save gameplay settings to storage
+    /// Also updates the cache with the new settings
+    pub fn save_gameplay_settings(settings: &GameplaySettings) {
+        let _ = Self::save_json(Self::GAMEPLAY_SETTINGS_KEY, "gameplay.json", settings);
+
+        // Update cache with the saved settings
+        if let Ok(mut cache) = GAMEPLAY_SETTINGS_CACHE.lock() {
+            *cache = Some(settings.clone());
+        }
+    }
+
+    /// Load key, mouse and touch bindings from storage
+    /// Results are cached after first load for performance
+    pub fn load_control_bindings() -> ControlBindings {
+        // Check cache first
+        if let Ok(cache) = CONTROL_BINDINGS_CACHE.lock() {
+            if let Some(cached) = cache.as_ref() {
+                return cached.clone();
+            }
+        }
+
+        let bindings = Self::load_json::<ControlBindings>(Self::CONTROL_BINDINGS_KEY, "controls.json")
+            .map(ControlBindings::with_missing_defaults)
+            .unwrap_or_default();
+
+        // Update cache
+        if let Ok(mut cache) = CONTROL_BINDINGS_CACHE.lock() {
+            *cache = Some(bindings.clone());
+        }
+
+        bindings
+    }
+
+    /// Save control bindings to storage
+    /// Also updates the cache with the new bindings
+    pub fn save_control_bindings(bindings: &ControlBindings) {
+        let _ = Self::save_json(Self::CONTROL_BINDINGS_KEY, "controls.json", bindings);
+
+        // Update cache with the saved bindings
+        if let Ok(mut cache) = CONTROL_BINDINGS_CACHE.lock() {
+            *cache = Some(bindings.clone());
+        }
+    }
+
+    /// Load the handling profile (DAS, ARR, soft drop) from storage
+    /// Results are cached after first load for performance
+    pub fn load_handling() -> HandlingProfile {
+        // Check cache first
+        if let Ok(cache) = HANDLING_CACHE.lock() {
+            if let Some(cached) = cache.as_ref() {
+                return *cached;
+            }
+        }
+
+        let handling = Self::load_json::<HandlingProfile>(Self::HANDLING_KEY, "handling.json")
+            .map(HandlingProfile::clamped)
+            .unwrap_or_default();
+
+        // Update cache
+        if let Ok(mut cache) = HANDLING_CACHE.lock() {
+            *cache = Some(handling);
+        }
+
+        handling
+    }
+
+    /// Save the handling profile to storage
+    /// Also updates the cache with the new profile
+    pub fn save_handling(handling: &HandlingProfile) {
+        let _ = Self::save_json(Self::HANDLING_KEY, "handling.json", handling);
+
+        // Update cache with the saved profile
+        if let Ok(mut cache) = HANDLING_CACHE.lock() {
+            *cache = Some(*handling);
+        }
+    }
+
+    /// Load the rules picked on the rules screen from storage
+    /// Results are cached after first load for performance
+    pub fn load_rule_options() -> RuleOptions {
+        // Check cache first
+        if let Ok(cache) = RULE_OPTIONS_CACHE.lock() {
+            if let Some(cached) = cache.as_ref() {
+                return *cached;
+            }
+        }
+
+        let options: RuleOptions =
+            Self::load_json(Self::RULE_OPTIONS_KEY, "rule_options.json").unwrap_or_default();
+
+        // Update cache
+        if let Ok(mut cache) = RULE_OPTIONS_CACHE.lock() {
+            *cache = Some(options);
+        }
+
+        options
+    }
+
+    /// Save the picked rules to storage
+    /// Also updates the cache with the new options
+    pub fn save_rule_options(options: &RuleOptions) {
+        let _ = Self::save_json(Self::RULE_OPTIONS_KEY, "rule_options.json", options);
+
+        // Update cache with the saved options
+        if let Ok(mut cache) = RULE_OPTIONS_CACHE.lock() {
+            *cache = Some(*options);
+        }
+    }
+
+    /// Load personal best times from storage
+    /// Results are cached after first load for performance
+    pub fn load_personal_bests() -> PersonalBests {
+        // Check cache first
+        if let Ok(cache) = PERSONAL_BESTS_CACHE.lock() {
+            if let Some(cached) = cache.as_ref() {
+                return cached.clone();
+            }
+        }
+
+        let bests: PersonalBests =
+            Self::load_json(Self::PERSONAL_BESTS_KEY, "personal_bests.json").unwrap_or_default();
+
+        // Update cache
+        if let Ok(mut cache) = PERSONAL_BESTS_CACHE.lock() {
+            *cache = Some(bests.clone());
+        }
+
+        bests
+    }
+
+    /// Save personal best times to storage
+    /// Also updates the cache with the new bests
+    pub fn save_personal_bests(bests: &PersonalBests) {
+        let _ = Self::save_json(Self::PERSONAL_BESTS_KEY, "personal_bests.json", bests);
+
+        // Update cache with the saved bests
+        if let Ok(mut cache) = PERSONAL_BESTS_CACHE.lock() {
+            *cache = Some(bests.clone());
+        }
+    }
+
+    /// Load the replay of the last finished game
+    /// Replays from another format version are ignored, they wouldn't play back the same
+    pub fn load_replay() -> Option<Replay> {
+        let replay: Replay = Self::load_json(Self::REPLAY_KEY, "last_replay.json")?;
+        if replay.version != REPLAY_VERSION {
+            println!(
+                "Ignoring replay saved with format version {} (current is {})",
+                replay.version, REPLAY_VERSION
+            );
+            return None;
+        }
+        if replay.rng != RNG_ALGORITHM {
+            println!(
+                "Ignoring replay recorded with the {:?} RNG (current is {:?})",
+                replay.rng, RNG_ALGORITHM
+            );
+            return None;
+        }
+        Some(replay)
+    }
+
+    /// Save a finished game as the last replay
+    pub fn save_replay(replay: &Replay) {
+        match Self::save_json(Self::REPLAY_KEY, "last_replay.json", replay) {
+            Ok(()) => println!(
+                "Saved replay: {} ticks, {} inputs",
+                replay.length_ticks,
+                replay.entries.len()
+            ),
+            Err(e) => eprintln!("Failed to save replay: {}", e),
+        }
+    }
+
+    // ===== Generic JSON helpers =====
+
+    /// Load a JSON value from localStorage (web) or the config directory (native)
+    /// `key` is the localStorage key, `file_name` the native config file
+    #[allow(unused_variables)]
+    fn load_json<T: DeserializeOwned>(key: &str, file_name: &str) -> Option<T> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::load_json_web(key)
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::load_json_native(file_name)
+        }
+    }
+
+    /// Save a JSON value to localStorage (web) or the config directory (native)
+    #[allow(unused_variables)]
+    fn save_json<T: Serialize>(key: &str, file_name: &str, value: &T) -> Result<(), String> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::save_json_web(key, value)
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::save_json_native(file_name, value)
+        }
+    }
+
     // ===== Web implementation (localStorage) =====
     
     #[cfg(target_arch = "wasm32")]
     const VOLUME_KEY: &'static str = "retris_volume_settings";
     #[cfg(target_arch = "wasm32")]
     const GAME_DATA_KEY: &'static str = "retris_game_data";
+    const GAMEPLAY_SETTINGS_KEY: &'static str = "retris_gameplay_settings";
+    const CONTROL_BINDINGS_KEY: &'static str = "retris_control_bindings";
+    const HANDLING_KEY: &'static str = "retris_handling";
+    const REPLAY_KEY: &'static str = "retris_last_replay";
+    const PERSONAL_BESTS_KEY: &'static str = "retris_personal_bests";
+    const RULE_OPTIONS_KEY: &'static str = "retris_rule_options";
+
+    #[cfg(target_arch = "wasm32")]
+    fn load_json_web<T: DeserializeOwned>(key: &str) -> Option<T> {
+        use web_sys::window;
+
+        let window = window()?;
+        let storage = window.local_storage().ok()??;
+        let json = storage.get_item(key).ok()??;
+
+        serde_json::from_str(&json).ok()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn save_json_web<T: Serialize>(key: &str, value: &T) -> Result<(), String> {
+        use web_sys::window;
+
+        let window = window().ok_or("No window")?;
+        let storage = window.local_storage()
+            .map_err(|_| "No localStorage")?
+            .ok_or("No localStorage")?;
+
+        let json = serde_json::to_string(value)
+            .map_err(|e| format!("Serialize error: {}", e))?;
+
+        storage.set_item(key, &json)
+            .map_err(|_| "Failed to set item".to_string())?;
+
+        println!("Saved {} to localStorage", key);
+        Ok(())
+    }
     
     #[cfg(target_arch = "wasm32")]
     fn load_volume_web() -> Option<VolumeSettings> {
@@ -239,6 +596,36 @@ impl Storage {
         None
     }
     
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_json_native<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+        let mut path = Self::config_path()?;
+        path.push(file_name);
+
+        let contents = std::fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_json_native<T: Serialize>(file_name: &str, value: &T) -> Result<(), String> {
+        let config_dir = Self::config_path().ok_or("No config directory")?;
+
+        // Create config directory if it doesn't exist
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+
+        let mut path = config_dir;
+        path.push(file_name);
+
+        let json = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Serialize error: {}", e))?;
+
+        std::fs::write(&path, json)
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+
+        println!("Saved {} to {:?}", file_name, path);
+        Ok(())
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn load_volume_native() -> Option<VolumeSettings> {
         let mut path = Self::config_path()?;
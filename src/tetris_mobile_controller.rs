@@ -3,28 +3,46 @@ use egor::input::{Input, MouseButton};
 use egor::math::{Vec2, vec2};
 use egor::render::{Color, Graphics};
 
+/// On-screen touch controls that can be bound to actions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MobileButton {
+    Left,     // Touch left of the piece
+    Right,    // Touch right of the piece
+    Rotate,   // Tap on the piece
+    SoftDrop, // Red button
+    HardDrop,
+    Hold,
+    Pause,
+}
+
 pub struct TetrisMobileController {
     screen_width: f32,
     screen_height: f32,
-    // Quit button (top center, Q)
-    quit_button_world_pos: Vec2,
-    quit_button_size: f32,
+    // Pause button (top center)
+    pause_button_world_pos: Vec2,
+    pause_button_size: f32,
     // Red button (under grid)
     red_button_world_pos: Vec2,
     red_button_size: f32,
+    // Hold button (under grid, left of red button)
+    hold_button_world_pos: Vec2,
+    // Hard drop button (under grid, right of red button)
+    hard_drop_button_world_pos: Vec2,
     // Button states
     left_held: bool,
     right_held: bool,
     rotate_pressed: bool,
-    quit_pressed: bool,
+    pause_pressed: bool,
     red_button_pressed: bool,
+    hold_pressed: bool,
+    hard_drop_pressed: bool,
     // Track if device is touch capable
     is_touch_capable: bool,
 }
 
 impl TetrisMobileController {
     // Base constants for UI sizing (normalized to 1048px screen height)
-    const BASE_QUIT_BUTTON_SIZE: f32 = 80.0;
+    const BASE_PAUSE_BUTTON_SIZE: f32 = 80.0;
     const BASE_RED_BUTTON_SIZE: f32 = 100.0;
     const BASE_BUTTON_BORDER_WIDTH: f32 = 4.0;
     const BASE_PADDING: f32 = 80.0;
@@ -38,15 +56,19 @@ impl TetrisMobileController {
         let mut controller = Self {
             screen_width,
             screen_height,
-            quit_button_world_pos: vec2(0.0, 0.0),
-            quit_button_size: 0.0,
+            pause_button_world_pos: vec2(0.0, 0.0),
+            pause_button_size: 0.0,
             red_button_world_pos: vec2(0.0, 0.0),
             red_button_size: 0.0,
+            hold_button_world_pos: vec2(0.0, 0.0),
+            hard_drop_button_world_pos: vec2(0.0, 0.0),
             left_held: false,
             right_held: false,
             rotate_pressed: false,
-            quit_pressed: false,
+            pause_pressed: false,
             red_button_pressed: false,
+            hold_pressed: false,
+            hard_drop_pressed: false,
             is_touch_capable: false,
         };
         controller.update_positions();
@@ -58,17 +80,19 @@ impl TetrisMobileController {
         let half_height = self.screen_height / 2.0;
         let scale = Self::scale_factor(self.screen_height);
 
-        self.quit_button_size = Self::BASE_QUIT_BUTTON_SIZE * scale;
+        self.pause_button_size = Self::BASE_PAUSE_BUTTON_SIZE * scale;
         self.red_button_size = Self::BASE_RED_BUTTON_SIZE * scale;
         let padding = Self::BASE_PADDING * scale;
 
-        // Quit button at top center
-        self.quit_button_world_pos =
-            vec2(0.0, -half_height + self.quit_button_size / 2.0 + padding);
+        // Pause button at top center
+        self.pause_button_world_pos =
+            vec2(0.0, -half_height + self.pause_button_size / 2.0 + padding);
 
         // Red button position will be set based on grid position (updated in update method)
         // For now, just initialize it
         self.red_button_world_pos = vec2(0.0, 0.0);
+        self.hold_button_world_pos = vec2(0.0, 0.0);
+        self.hard_drop_button_world_pos = vec2(0.0, 0.0);
     }
 
     pub fn update(
@@ -104,14 +128,26 @@ impl TetrisMobileController {
                 0.0,
                 grid_bottom + button_spacing + self.red_button_size / 2.0,
             );
+            // Hold button sits to the left of the red button on the same row
+            self.hold_button_world_pos = vec2(
+                self.red_button_world_pos.x - self.red_button_size - button_spacing,
+                self.red_button_world_pos.y,
+            );
+            // Hard drop button mirrors it on the right
+            self.hard_drop_button_world_pos = vec2(
+                self.red_button_world_pos.x + self.red_button_size + button_spacing,
+                self.red_button_world_pos.y,
+            );
         }
 
         // Reset button states
         self.rotate_pressed = false;
-        self.quit_pressed = false;
+        self.pause_pressed = false;
         self.left_held = false;
         self.right_held = false;
         self.red_button_pressed = false;
+        self.hold_pressed = false;
+        self.hard_drop_pressed = false;
 
         let coords = CoordinateSystem::with_default_offset(self.screen_width, self.screen_height);
 
@@ -167,21 +203,45 @@ impl TetrisMobileController {
         // Convert buffer coordinates to world coordinates for comparison
         let touch_world = coords.screen_to_world(vec2(x, y));
 
-        // Check quit button first (has priority) - use world coordinates
-        let quit_half = self.quit_button_size / 2.0;
-        if touch_world.x >= self.quit_button_world_pos.x - quit_half
-            && touch_world.x <= self.quit_button_world_pos.x + quit_half
-            && touch_world.y >= self.quit_button_world_pos.y - quit_half
-            && touch_world.y <= self.quit_button_world_pos.y + quit_half
+        // Check pause button first (has priority) - use world coordinates
+        let pause_half = self.pause_button_size / 2.0;
+        if touch_world.x >= self.pause_button_world_pos.x - pause_half
+            && touch_world.x <= self.pause_button_world_pos.x + pause_half
+            && touch_world.y >= self.pause_button_world_pos.y - pause_half
+            && touch_world.y <= self.pause_button_world_pos.y + pause_half
         {
             if just_pressed {
-                self.quit_pressed = true;
+                self.pause_pressed = true;
             }
             return;
         }
 
-        // Check red button (fast drop) - use world coordinates
+        // Check hold button - use world coordinates (same size as red button)
         let red_half = self.red_button_size / 2.0;
+        if touch_world.x >= self.hold_button_world_pos.x - red_half
+            && touch_world.x <= self.hold_button_world_pos.x + red_half
+            && touch_world.y >= self.hold_button_world_pos.y - red_half
+            && touch_world.y <= self.hold_button_world_pos.y + red_half
+        {
+            if just_pressed {
+                self.hold_pressed = true;
+            }
+            return;
+        }
+
+        // Check hard drop button - use world coordinates (same size as red button)
+        if touch_world.x >= self.hard_drop_button_world_pos.x - red_half
+            && touch_world.x <= self.hard_drop_button_world_pos.x + red_half
+            && touch_world.y >= self.hard_drop_button_world_pos.y - red_half
+            && touch_world.y <= self.hard_drop_button_world_pos.y + red_half
+        {
+            if just_pressed {
+                self.hard_drop_pressed = true;
+            }
+            return;
+        }
+
+        // Check red button (fast drop) - use world coordinates
         if touch_world.x >= self.red_button_world_pos.x - red_half
             && touch_world.x <= self.red_button_world_pos.x + red_half
             && touch_world.y >= self.red_button_world_pos.y - red_half
@@ -241,8 +301,8 @@ impl TetrisMobileController {
     pub fn draw(&self, gfx: &mut Graphics) {
         let coords = CoordinateSystem::with_default_offset(self.screen_width, self.screen_height);
 
-        // Draw quit button (top center)
-        self.draw_quit_button(gfx, &coords, self.quit_button_world_pos);
+        // Draw pause button (top center)
+        self.draw_pause_button(gfx, &coords, self.pause_button_world_pos);
 
         // Draw red button (under grid)
         self.draw_bottom_button(
@@ -252,6 +312,46 @@ impl TetrisMobileController {
             Color::new([1.0, 0.2, 0.2, 0.4]),
             Color::new([1.0, 0.4, 0.4, 0.6]),
         );
+
+        // Draw hold button (left of red button)
+        self.draw_bottom_button(
+            gfx,
+            &coords,
+            self.hold_button_world_pos,
+            Color::new([0.2, 0.4, 1.0, 0.4]),
+            Color::new([0.4, 0.6, 1.0, 0.6]),
+        );
+        self.draw_button_label(gfx, &coords, self.hold_button_world_pos, "H");
+
+        // Draw hard drop button (right of red button)
+        self.draw_bottom_button(
+            gfx,
+            &coords,
+            self.hard_drop_button_world_pos,
+            Color::new([0.2, 0.9, 0.4, 0.4]),
+            Color::new([0.4, 1.0, 0.6, 0.6]),
+        );
+        self.draw_button_label(gfx, &coords, self.hard_drop_button_world_pos, "D");
+    }
+
+    /// Draw a single-letter label on a bottom button
+    fn draw_button_label(
+        &self,
+        gfx: &mut Graphics,
+        coords: &CoordinateSystem,
+        world_pos: Vec2,
+        label: &str,
+    ) {
+        // Convert to screen coordinates for text, offset so the letter sits roughly centered
+        let text_size = self.red_button_size * 0.5;
+        let screen_pos = coords.world_to_screen(vec2(
+            world_pos.x - text_size * 0.25,
+            world_pos.y - text_size * 0.5,
+        ));
+        gfx.text(label)
+            .at(screen_pos)
+            .size(text_size)
+            .color(Color::WHITE);
     }
 
     fn draw_bottom_button(
@@ -297,8 +397,8 @@ impl TetrisMobileController {
             .color(border_color);
     }
 
-    fn draw_quit_button(&self, gfx: &mut Graphics, coords: &CoordinateSystem, world_pos: Vec2) {
-        let size = self.quit_button_size;
+    fn draw_pause_button(&self, gfx: &mut Graphics, _coords: &CoordinateSystem, world_pos: Vec2) {
+        let size = self.pause_button_size;
         let half_size = size / 2.0;
         let border_width = Self::BASE_BUTTON_BORDER_WIDTH * Self::scale_factor(self.screen_height);
 
@@ -338,33 +438,27 @@ impl TetrisMobileController {
             .size(vec2(border_width, size))
             .color(border_color);
 
-        // Label text "Q" - convert to screen coordinates for text
-        let screen_pos = coords.world_to_screen(world_pos);
-        let text_size = size * 0.6;
-        gfx.text("Q")
-            .at(vec2(screen_pos.x, screen_pos.y))
-            .size(text_size)
-            .color(Color::WHITE);
-    }
-
-    // Getters for input states
-    pub fn left_held(&self) -> bool {
-        self.left_held
-    }
-
-    pub fn right_held(&self) -> bool {
-        self.right_held
-    }
-
-    pub fn rotate_pressed(&self) -> bool {
-        self.rotate_pressed
-    }
-
-    pub fn quit_pressed(&self) -> bool {
-        self.quit_pressed
+        // Pause icon - two vertical bars in world coordinates
+        let bar_width = size * 0.15;
+        let bar_height = size * 0.5;
+        for bar_x in [world_pos.x - bar_width * 1.5, world_pos.x + bar_width * 0.5] {
+            gfx.rect()
+                .at(vec2(bar_x, world_pos.y - bar_height / 2.0))
+                .size(vec2(bar_width, bar_height))
+                .color(Color::WHITE);
+        }
     }
 
-    pub fn red_button_pressed(&self) -> bool {
-        self.red_button_pressed
+    /// Whether a touch control is active this frame (taps report only the frame they happen)
+    pub fn is_active(&self, button: MobileButton) -> bool {
+        match button {
+            MobileButton::Left => self.left_held,
+            MobileButton::Right => self.right_held,
+            MobileButton::Rotate => self.rotate_pressed,
+            MobileButton::SoftDrop => self.red_button_pressed,
+            MobileButton::HardDrop => self.hard_drop_pressed,
+            MobileButton::Hold => self.hold_pressed,
+            MobileButton::Pause => self.pause_pressed,
+        }
     }
 }
@@ -1,7 +1,11 @@
 use crate::coordinate_system::CoordinateSystem;
 use crate::retris_colors::*;
+use crate::controls::{Action, ControlBindings};
+use crate::game_mode::GameMode;
+use crate::retris_ui::Button;
+use crate::storage::Storage;
 use crate::tetris_shape::{ShapeName, TetrisShapeNode};
-use egor::input::{Input, KeyCode};
+use egor::input::Input;
 use egor::math::vec2;
 use egor::render::Graphics;
 
@@ -16,6 +20,15 @@ const LETTER_WIDTH_CELLS: f32 = 3.0;
 const PADDING_PERCENT: f32 = 0.15; // 15% padding on each side (total 30% of width)
 const MAX_HEIGHT_PERCENT: f32 = 0.6; // Logo + instructions should take max 60% of height
 
+/// Y position of the first instruction line, and the spacing between lines
+const INSTRUCTIONS_Y: f32 = TARGET_Y + 150.0;
+const LINE_HEIGHT: f32 = 35.0;
+/// Which instruction line shows the mode, with the < / > touch buttons beside it
+const MODE_LINE: usize = 7;
+/// Size of the < / > buttons and their distance from the center
+const MODE_BUTTON_SIZE: f32 = 40.0;
+const MODE_BUTTON_X: f32 = 280.0;
+
 
 /// Get a color from the piece colors array by index
 fn get_piece_color(index: usize) -> egor::render::Color {
@@ -32,14 +45,15 @@ pub struct TitleScreen {
     rotation_velocity: f32,   // Rotation velocity for spin animation
     float_timer: f32,         // Timer for floating animation
     high_score: u64,          // High score to display
+    mode: GameMode,           // Mode the next game is played in
+    previous_mode_button: Button,
+    next_mode_button: Button,
+    screen_width: f32,  // Last size seen by update_screen_size (for button clicks)
+    screen_height: f32,
 }
 
 impl TitleScreen {
     pub fn new() -> Self {
-        use crate::storage::Storage;
-
-        let game_data = Storage::load_game_data();
-
         // Use default screen dimensions for initial calculation (will be updated via update_screen_size)
         let default_width = 640.0;
         let default_height = 1048.0;
@@ -70,8 +84,8 @@ impl TitleScreen {
             (ShapeName::new_letter_e(), get_piece_color(1)), // Yellow
             (ShapeName::new_letter_t(), get_piece_color(2)), // Magenta
             (ShapeName::new_letter_r(), get_piece_color(3)), // Orange
-            (ShapeName::new_letter_i(), get_piece_color(4)), // Green
-            (ShapeName::new_letter_s(), get_piece_color(0)), // Cyan
+            (ShapeName::new_letter_i(), get_piece_color(5)), // Green
+            (ShapeName::new_letter_s(), get_piece_color(6)), // Red
         ];
 
         let num_letters = letter_shapes.len() as f32;
@@ -82,10 +96,6 @@ impl TitleScreen {
         let start_x = coords.center_x() - total_width / 2.0 + letter_width / 2.0;
         let start_y = TARGET_Y; // Center vertically
 
-        // Create a small virtual grid just for the shape positioning system
-        let grid_width_cells = 200;
-        let grid_height_cells = 100;
-
         // Position grid using coordinate system's left edge (respecting aspect ratio)
         // The grid_position is the world position of grid cell (0,0)
         // We use the left edge of the playing field (respecting aspect ratio) as reference
@@ -101,21 +111,15 @@ impl TitleScreen {
             let cell_x = ((current_x_world - grid_position.x) / cell_size) as i32;
             let cell_y = -2;
 
-            let mut letter = TetrisShapeNode::new_with_shape_and_color(
-                0,
+            let letter = TetrisShapeNode::new_with_shape_and_color(
                 cell_x,
                 cell_y,
                 cell_size,
                 grid_position,
-                grid_width_cells,
-                grid_height_cells,
                 shape_name,
                 color,
             );
 
-            // Mark as already stopped (no falling animation)
-            letter.stopped = true;
-
             letters.push(letter);
 
             // Move to next letter position
@@ -131,13 +135,41 @@ impl TitleScreen {
             rotation_angle: 0.0,
             rotation_velocity: 0.0,
             float_timer: 0.0,
-            high_score: game_data.high_score,
+            high_score: Self::saved_high_score(),
+            mode: GameMode::default(),
+            previous_mode_button: Button::new(0.0, 0.0, 0.0, 0.0, "<"),
+            next_mode_button: Button::new(0.0, 0.0, 0.0, 0.0, ">"),
+            screen_width: default_width,
+            screen_height: default_height,
         }
     }
 
+    /// Text size of the instruction lines (roughly 2% of screen height)
+    fn text_size(screen_height: f32) -> f32 {
+        (screen_height * 0.018).clamp(25.0, 54.0)
+    }
+
     /// Update title screen positions based on actual screen dimensions
     pub fn update_screen_size(&mut self, screen_width: f32, screen_height: f32) {
         let coords = CoordinateSystem::with_default_offset(screen_width, screen_height);
+        self.screen_width = screen_width;
+        self.screen_height = screen_height;
+
+        // Mode buttons sit either side of the mode line, vertically centered on its text
+        let mode_line_y = INSTRUCTIONS_Y + MODE_LINE as f32 * LINE_HEIGHT;
+        let button_y = mode_line_y + (Self::text_size(screen_height) - MODE_BUTTON_SIZE) / 2.0;
+        self.previous_mode_button.set_position(
+            -MODE_BUTTON_X,
+            button_y,
+            MODE_BUTTON_SIZE,
+            MODE_BUTTON_SIZE,
+        );
+        self.next_mode_button.set_position(
+            MODE_BUTTON_X - MODE_BUTTON_SIZE,
+            button_y,
+            MODE_BUTTON_SIZE,
+            MODE_BUTTON_SIZE,
+        );
         
         // Calculate cell_size based on both width and height constraints
         let total_cells = (NUM_LETTERS * LETTER_WIDTH_CELLS) + ((NUM_LETTERS - 1.0) * LETTER_SPACING);
@@ -160,8 +192,6 @@ impl TitleScreen {
         let total_width = (num_letters * letter_width) + ((num_letters - 1.0) * letter_spacing);
         let start_x = coords.center_x() - total_width / 2.0 + letter_width / 2.0;
         let start_y = TARGET_Y;
-        let grid_width_cells = 200;
-        let grid_height_cells = 100;
 
         let left_edge = coords.left_edge_x();
 
@@ -180,39 +210,50 @@ impl TitleScreen {
             letter.cell_y = cell_y;
             letter.cell_size = cell_size;
             letter.grid_position = grid_position;
-            letter.grid_width_cells = grid_width_cells;
-            letter.grid_height_cells = grid_height_cells;
 
             // Move to next letter position
             current_x_world += letter_width + letter_spacing;
         }
     }
 
-    pub fn update(&mut self, input: &Input, fixed_delta: f32) {
+    /// Animate the logo and handle the title screen controls
+    /// Returns true if a click went to the mode buttons (so it shouldn't also start a game)
+    pub fn update(&mut self, input: &Input, bindings: &ControlBindings, fixed_delta: f32) -> bool {
         // Update floating animation timer
         self.float_timer += fixed_delta;
 
         // Handle interactive controls
 
-        // Arrow Left: bounce left
-        if input.key_pressed(KeyCode::ArrowLeft) {
+        // Rotate or tap < / >: pick the mode
+        let (width, height) = (self.screen_width, self.screen_height);
+        let previous_clicked = self.previous_mode_button.is_clicked(input, width, height);
+        let next_clicked = self.next_mode_button.is_clicked(input, width, height);
+        if next_clicked || bindings.pressed(Action::RotateClockwise, input, None) {
+            self.mode = self.mode.next();
+        }
+        if previous_clicked || bindings.pressed(Action::RotateCounterClockwise, input, None) {
+            self.mode = self.mode.previous();
+        }
+
+        // Move left: bounce left
+        if bindings.pressed(Action::MoveLeft, input, None) {
             self.horizontal_offset = -30.0; // Shift left
             self.horizontal_velocity = 0.0;
         }
 
-        // Arrow Right: bounce right
-        if input.key_pressed(KeyCode::ArrowRight) {
+        // Move right: bounce right
+        if bindings.pressed(Action::MoveRight, input, None) {
             self.horizontal_offset = 30.0; // Shift right
             self.horizontal_velocity = 0.0;
         }
 
-        // Space: spin
-        if input.key_pressed(KeyCode::Space) {
+        // Hard drop: spin
+        if bindings.pressed(Action::HardDrop, input, None) {
             self.rotation_velocity += std::f32::consts::TAU * 2.0; // Add one full rotation
         }
 
-        // Arrow Down: drop and bounce
-        if input.key_pressed(KeyCode::ArrowDown) {
+        // Soft drop: drop and bounce
+        if bindings.pressed(Action::SoftDrop, input, None) {
             self.vertical_offset = 50.0; // Drop down
             self.vertical_velocity = 0.0;
         }
@@ -261,9 +302,11 @@ impl TitleScreen {
             self.rotation_velocity = 0.0;
             self.rotation_angle = 0.0; // Reset to upright
         }
+
+        previous_clicked || next_clicked
     }
 
-    pub fn draw(&mut self, gfx: &mut Graphics, _alpha: f32) {
+    pub fn draw(&mut self, gfx: &mut Graphics, bindings: &ControlBindings, _alpha: f32) {
         let screen = gfx.screen_size();
         let screen_width = screen.x;
         let screen_height = screen.y;
@@ -319,20 +362,35 @@ impl TitleScreen {
 
         // Draw instructions in green text below the title
         // Since (0,0) is the center of the screen, position text relative to center
-        let instructions_y = TARGET_Y + 150.0;
+        let instructions_y = INSTRUCTIONS_Y;
 
         // Calculate text size based on screen height (roughly 2.5% of screen height)
-        let text_size = (screen_height * 0.018).max(25.0).min(54.0);
+        let text_size = Self::text_size(screen_height);
 
+        // Key names come from the player's bindings
+        let key = |action| bindings.describe(action);
         let instructions = [
-            "Arrow Left/Right: Move",
-            "Arrow Down: Speed Up",
-            "Space: Rotate",
-            "",
-            "Press Enter to Start",
+            format!(
+                "{} / {}: Move",
+                key(Action::MoveLeft),
+                key(Action::MoveRight)
+            ),
+            format!("{}: Speed Up", key(Action::SoftDrop)),
+            format!("{}: Hard Drop", key(Action::HardDrop)),
+            format!("{}: Rotate Right", key(Action::RotateClockwise)),
+            format!(
+                "{}: Rotate Left   {}: Flip",
+                key(Action::RotateCounterClockwise),
+                key(Action::Rotate180)
+            ),
+            format!("{}: Hold   {}: Pause", key(Action::Hold), key(Action::Pause)),
+            String::new(),
+            // Line MODE_LINE, between the < / > buttons
+            format!("Mode: {}  (rotate to change)", self.mode.name()),
+            format!("Press {} to Start", key(Action::Start)),
         ];
 
-        let line_height = 35.0;
+        let line_height = LINE_HEIGHT;
         let start_y = instructions_y;
 
         for (i, line) in instructions.iter().enumerate() {
@@ -352,6 +410,25 @@ impl TitleScreen {
                 .size(text_size)
                 .color(COLOR_TEXT_GREEN);
         }
+
+        // Touch targets for changing the mode
+        self.previous_mode_button.draw(gfx, screen_width, screen_height);
+        self.next_mode_button.draw(gfx, screen_width, screen_height);
+    }
+
+    /// High score for the scoring rule picked on the rules screen
+    fn saved_high_score() -> u64 {
+        Storage::load_game_data().high_score_for(Storage::load_rule_options().scoring_rule)
+    }
+
+    /// Reload the high score after a game or a change of scoring rule
+    pub fn refresh_high_score(&mut self) {
+        self.high_score = Self::saved_high_score();
+    }
+
+    /// Mode the next game should be played in
+    pub fn mode(&self) -> GameMode {
+        self.mode
     }
 
     fn draw_letter_with_transform(
@@ -3,17 +3,23 @@ use crate::music_manager::MusicManager;
 use crate::retris_colors::*;
 use crate::retris_ui::{Button, VolumeSlider};
 use crate::sound_manager::SoundManager;
+use crate::storage::{GameplaySettings, Storage};
 use crate::volume_manager::VolumeManager;
 use egor::input::Input;
 use egor::math::vec2;
 use egor::render::Graphics;
 
-/// Volume control screen - accessible from anywhere in the game
+/// Volume control and settings screen - accessible from anywhere in the game
 pub struct VolumeControlScreen {
     music_slider: VolumeSlider,
     sfx_slider: VolumeSlider,
+    ghost_toggle: Button,
+    controls_button: Button,
+    handling_button: Button,
+    rules_button: Button,
     close_button: Button,
     test_sound_timer: f32,
+    gameplay_settings: GameplaySettings,
 }
 
 impl VolumeControlScreen {
@@ -37,12 +43,32 @@ impl VolumeControlScreen {
     /// Base button X position (normalized to 1048px height)
     const BASE_BUTTON_X: f32 = -75.0;
     /// Base button Y position (normalized to 1048px height)
-    const BASE_BUTTON_Y: f32 = 150.0;
+    const BASE_BUTTON_Y: f32 = 420.0;
+    /// Base toggle button height (normalized to 1048px height)
+    const BASE_TOGGLE_HEIGHT: f32 = 50.0;
+    /// Base ghost toggle Y position (normalized to 1048px height)
+    const BASE_GHOST_TOGGLE_Y: f32 = 130.0;
+    /// Base controls button Y position (normalized to 1048px height)
+    const BASE_CONTROLS_BUTTON_Y: f32 = 200.0;
+    /// Base handling button Y position (normalized to 1048px height)
+    const BASE_HANDLING_BUTTON_Y: f32 = 270.0;
+    /// Base rules button Y position (normalized to 1048px height)
+    const BASE_RULES_BUTTON_Y: f32 = 340.0;
+
+    /// Label for the ghost piece toggle
+    fn ghost_label(show_ghost: bool) -> &'static str {
+        if show_ghost {
+            "Ghost Piece: On"
+        } else {
+            "Ghost Piece: Off"
+        }
+    }
     
     pub fn new(volume_manager: &VolumeManager) -> Self {
         // Use default screen dimensions for initial calculation (will be updated via update)
         let default_height = 1048.0;
         let scale = Self::scale_factor(default_height);
+        let gameplay_settings = Storage::load_gameplay_settings();
         
         Self {
             music_slider: VolumeSlider::new(
@@ -59,6 +85,34 @@ impl VolumeControlScreen {
                 "Sound Effects Volume",
                 volume_manager.sfx_volume(),
             ),
+            ghost_toggle: Button::new(
+                Self::BASE_SLIDER_X * scale,
+                Self::BASE_GHOST_TOGGLE_Y * scale,
+                Self::BASE_SLIDER_WIDTH * scale,
+                Self::BASE_TOGGLE_HEIGHT * scale,
+                Self::ghost_label(gameplay_settings.show_ghost),
+            ),
+            controls_button: Button::new(
+                Self::BASE_SLIDER_X * scale,
+                Self::BASE_CONTROLS_BUTTON_Y * scale,
+                Self::BASE_SLIDER_WIDTH * scale,
+                Self::BASE_TOGGLE_HEIGHT * scale,
+                "Controls",
+            ),
+            handling_button: Button::new(
+                Self::BASE_SLIDER_X * scale,
+                Self::BASE_HANDLING_BUTTON_Y * scale,
+                Self::BASE_SLIDER_WIDTH * scale,
+                Self::BASE_TOGGLE_HEIGHT * scale,
+                "Handling",
+            ),
+            rules_button: Button::new(
+                Self::BASE_SLIDER_X * scale,
+                Self::BASE_RULES_BUTTON_Y * scale,
+                Self::BASE_SLIDER_WIDTH * scale,
+                Self::BASE_TOGGLE_HEIGHT * scale,
+                "Rules",
+            ),
             close_button: Button::new(
                 Self::BASE_BUTTON_X * scale,
                 Self::BASE_BUTTON_Y * scale,
@@ -67,6 +121,7 @@ impl VolumeControlScreen {
                 "Close",
             ),
             test_sound_timer: 0.0,
+            gameplay_settings,
         }
     }
 
@@ -80,7 +135,7 @@ impl VolumeControlScreen {
         volume_manager: &VolumeManager,
         screen_width: f32,
         screen_height: f32,
-    ) -> bool {
+    ) -> SettingsAction {
         music_manager.stop();
         // Update test sound timer
         self.test_sound_timer += delta;
@@ -105,6 +160,30 @@ impl VolumeControlScreen {
             Self::BASE_SFX_SLIDER_Y * scale,
             Self::BASE_SLIDER_WIDTH * scale,
         );
+        self.ghost_toggle.set_position(
+            Self::BASE_SLIDER_X * scale,
+            Self::BASE_GHOST_TOGGLE_Y * scale,
+            Self::BASE_SLIDER_WIDTH * scale,
+            Self::BASE_TOGGLE_HEIGHT * scale,
+        );
+        self.controls_button.set_position(
+            Self::BASE_SLIDER_X * scale,
+            Self::BASE_CONTROLS_BUTTON_Y * scale,
+            Self::BASE_SLIDER_WIDTH * scale,
+            Self::BASE_TOGGLE_HEIGHT * scale,
+        );
+        self.handling_button.set_position(
+            Self::BASE_SLIDER_X * scale,
+            Self::BASE_HANDLING_BUTTON_Y * scale,
+            Self::BASE_SLIDER_WIDTH * scale,
+            Self::BASE_TOGGLE_HEIGHT * scale,
+        );
+        self.rules_button.set_position(
+            Self::BASE_SLIDER_X * scale,
+            Self::BASE_RULES_BUTTON_Y * scale,
+            Self::BASE_SLIDER_WIDTH * scale,
+            Self::BASE_TOGGLE_HEIGHT * scale,
+        );
         self.close_button.set_position(
             Self::BASE_BUTTON_X * scale,
             Self::BASE_BUTTON_Y * scale,
@@ -115,6 +194,10 @@ impl VolumeControlScreen {
         // Update slider and button internal state
         self.music_slider.update(screen_width, screen_height);
         self.sfx_slider.update(screen_width, screen_height);
+        self.ghost_toggle.update(screen_width, screen_height);
+        self.controls_button.update(screen_width, screen_height);
+        self.handling_button.update(screen_width, screen_height);
+        self.rules_button.update(screen_width, screen_height);
         self.close_button.update(screen_width, screen_height);
 
         // Handle music slider input
@@ -142,12 +225,37 @@ impl VolumeControlScreen {
             volume_manager.save();
         }
 
-        // Return true if user clicked Close button
+        // Toggle the ghost piece and persist immediately
+        if self.ghost_toggle.is_clicked(input, screen_width, screen_height) {
+            self.gameplay_settings.show_ghost = !self.gameplay_settings.show_ghost;
+            self.ghost_toggle
+                .set_label(Self::ghost_label(self.gameplay_settings.show_ghost));
+            Storage::save_gameplay_settings(&self.gameplay_settings);
+        }
+
+        // Controls, handling and rules get their own screens
+        if self
+            .controls_button
+            .is_clicked(input, screen_width, screen_height)
+        {
+            return SettingsAction::Controls;
+        }
+        if self
+            .handling_button
+            .is_clicked(input, screen_width, screen_height)
+        {
+            return SettingsAction::Handling;
+        }
+        if self.rules_button.is_clicked(input, screen_width, screen_height) {
+            return SettingsAction::Rules;
+        }
+
+        // Close returns to wherever settings were opened from
         if self.close_button.is_clicked(input, screen_width, screen_height) {
             music_manager.start();
-            true
+            SettingsAction::Close
         } else {
-            false
+            SettingsAction::None
         }
     }
 
@@ -169,12 +277,18 @@ impl VolumeControlScreen {
         // Draw title - scaled position and size
         let title_y = -200.0 * scale;
         let title_size = (screen_height * 0.046).max(32.0).min(80.0); // Scaled text size
-        self.draw_centered_text(gfx, "VOLUME CONTROL", title_y, title_size, COLOR_TEXT_GREEN, screen_width, screen_height);
+        self.draw_centered_text(gfx, "SETTINGS", title_y, title_size, COLOR_TEXT_GREEN, screen_width, screen_height);
 
         // Draw sliders
         self.music_slider.draw(gfx, screen_width, screen_height);
         self.sfx_slider.draw(gfx, screen_width, screen_height);
 
+        // Draw gameplay toggles
+        self.ghost_toggle.draw(gfx, screen_width, screen_height);
+        self.controls_button.draw(gfx, screen_width, screen_height);
+        self.handling_button.draw(gfx, screen_width, screen_height);
+        self.rules_button.draw(gfx, screen_width, screen_height);
+
         // Draw close button
         self.close_button.draw(gfx, screen_width, screen_height);
     }
@@ -198,3 +312,12 @@ impl VolumeControlScreen {
         gfx.text(text).at(screen_pos).size(size).color(color);
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsAction {
+    None,
+    Controls,
+    Handling,
+    Rules,
+    Close,
+}